@@ -0,0 +1,43 @@
+//! Compares the queue-driven `ConstraintPropagationStrategy` with the rescanning
+//! propagation it replaced. The queue also places hidden singles, so it may solve more
+//! slots.
+//!
+//! Run with `cargo run --release --example propagation_bench`.
+use std::time::{Duration, Instant};
+use sudoku_solver::objects::sudoku::Sudoku9x9;
+use sudoku_solver::parsers::load_csv;
+use sudoku_solver::strategies::{ConstraintPropagationStrategy, RescanPropagationStrategy};
+use sudoku_solver::traits::{SimpleSudoku, SolveMetrics, Strategy};
+
+const ITERATIONS: u32 = 200;
+const FIXTURES: [&str; 4] = [
+    "fixtures/easy.csv",
+    "fixtures/average.csv",
+    "fixtures/hard_1.csv",
+    "fixtures/hard_2.csv",
+];
+
+/// Average time of a run, grid construction excluded, with the solved slots left.
+fn bench(filename: &str, strategy: &dyn Strategy<Sudoku9x9>) -> (Duration, usize) {
+    let grid = load_csv(filename);
+    let mut total = Duration::ZERO;
+    let mut solved = 0;
+    for _ in 0..ITERATIONS {
+        let mut sudoku = Sudoku9x9::new(grid.clone());
+        let start = Instant::now();
+        strategy.run(&mut sudoku);
+        total += start.elapsed();
+        solved = sudoku.count_solved_slots();
+    }
+    (total / ITERATIONS, solved)
+}
+
+fn main() {
+    println!("{:<24} {:>16} {:>16}", "fixture", "rescan", "queue");
+    for filename in FIXTURES {
+        let rescan = bench(filename, &RescanPropagationStrategy);
+        let queue = bench(filename, &ConstraintPropagationStrategy);
+        let cell = |(time, solved): (Duration, usize)| format!("{time:>9.1?} ({solved:>2})");
+        println!("{:<24} {:>16} {:>16}", filename, cell(rescan), cell(queue));
+    }
+}
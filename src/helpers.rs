@@ -1,7 +1,11 @@
+use crate::objects::deduction::{Deduction, apply_deductions};
 use crate::objects::slot::Slot;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
 use std::cell::RefCell;
 use std::collections::HashSet;
+use std::fmt::Display;
 use std::rc::Rc;
+use tracing::{debug, info};
 
 pub fn has_duplicate_values(slots: &Vec<Rc<RefCell<Slot>>>) -> bool {
     let mut seen_values = HashSet::new();
@@ -18,3 +22,149 @@ pub fn has_duplicate_values(slots: &Vec<Rc<RefCell<Slot>>>) -> bool {
     }
     false
 }
+
+/// Ids of all peers (slots sharing a row, column or area) of the slot.
+pub fn peer_ids(slot: &Slot) -> HashSet<usize> {
+    slot.get_peers()
+        .iter()
+        .map(|peer| peer.borrow().id())
+        .collect()
+}
+
+/// Unsolved slots of the unit that can still hold the value.
+pub fn positions_of(slots: &[Rc<RefCell<Slot>>], value: u8) -> Vec<usize> {
+    slots
+        .iter()
+        .filter(|slot| {
+            let slot = slot.borrow();
+            !slot.is_solved() && slot.candidates().contains(&value)
+        })
+        .map(|slot| slot.borrow().id())
+        .collect()
+}
+
+/// Ids of the row, column and area the slot belongs to.
+pub fn position(slot: &Slot) -> (usize, usize, usize) {
+    let unit_id = |id: Option<usize>| id.expect("Slot is not attached to a sudoku.");
+    (
+        unit_id(slot.row().upgrade().map(|row| row.borrow().id())),
+        unit_id(slot.column().upgrade().map(|column| column.borrow().id())),
+        unit_id(slot.area().upgrade().map(|area| area.borrow().id())),
+    )
+}
+
+/// Name of the slot in r1c1 notation.
+pub fn cell_name<S: SimpleSudoku>(sudoku: &S, slot_id: usize) -> String {
+    let (row, column, _) = position(&sudoku.slots()[slot_id - 1].borrow());
+    format!("r{row}c{column}")
+}
+
+/// Digits set in a candidate bit mask, where bit `d` stands for digit `d`.
+pub fn digits(mask: u32) -> impl Iterator<Item = u8> {
+    (1..32u8).filter(move |value| mask & (1 << value) != 0)
+}
+
+/// All subsets of `items` with 1 to `max_size` elements.
+pub fn subsets(items: &[usize], max_size: usize) -> Vec<Vec<usize>> {
+    let mut result: Vec<Vec<usize>> = vec![Vec::new()];
+    for &item in items {
+        let grown: Vec<Vec<usize>> = result
+            .iter()
+            .filter(|subset| subset.len() < max_size)
+            .map(|subset| {
+                let mut subset = subset.clone();
+                subset.push(item);
+                subset
+            })
+            .collect();
+        result.extend(grown);
+    }
+    result.retain(|subset| !subset.is_empty());
+    result
+}
+
+/// Run a strategy by applying all the deductions it finds on the current grid.
+pub fn run_deductions<S: SimpleSudoku>(strategy: &(impl Strategy<S> + Display), sudoku: &mut S) {
+    run_found_deductions(strategy, sudoku, |sudoku| strategy.deductions(sudoku));
+}
+
+/// Run a strategy by applying the deductions `find` returns, e.g. the ones found
+/// before a budget ran out.
+pub fn run_found_deductions<S: SimpleSudoku>(
+    strategy: &impl Display,
+    sudoku: &mut S,
+    find: impl FnOnce(&S) -> Vec<Deduction>,
+) {
+    info!("{}: started.", strategy);
+    let deductions = find(sudoku);
+    for deduction in &deductions {
+        debug!("{}: found {}.", strategy, deduction);
+    }
+    apply_deductions(sudoku, &deductions);
+    debug!("{}: completed.", strategy);
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use crate::objects::deduction::Deduction;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::solver::Solver;
+    use crate::strategies::{
+        ConstraintPropagationStrategy, HiddenSingleInColumnStrategy, HiddenSingleInRowStrategy,
+    };
+    use crate::traits::{HasSlots, SimpleSudoku};
+
+    /// Load the fixture and solve it as far as singles go.
+    pub fn singles_solved(filename: &str) -> Sudoku9x9 {
+        let mut sudoku = Sudoku9x9::new(load_csv(filename));
+        let mut solver = Solver::new(&mut sudoku);
+        solver.add_strategy(Box::new(ConstraintPropagationStrategy));
+        solver.add_strategy(Box::new(HiddenSingleInRowStrategy));
+        solver.add_strategy(Box::new(HiddenSingleInColumnStrategy));
+        solver.solve();
+        sudoku
+    }
+
+    /// Empty grid where the given slots are restricted to the given candidates.
+    pub fn with_candidates(cells: &[(usize, &[u8])]) -> Sudoku9x9 {
+        let sudoku = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        for &(slot_id, candidates) in cells {
+            let excluded: Vec<u8> = (1..=9).filter(|c| !candidates.contains(c)).collect();
+            sudoku.slots()[slot_id - 1]
+                .borrow_mut()
+                .exclude_values(excluded);
+        }
+        sudoku
+    }
+
+    /// Candidates of a slot that can't hold 1.
+    pub const WITHOUT_ONE: &[u8] = &[2, 3, 4, 5, 6, 7, 8, 9];
+
+    /// Empty grid where 1 is removed from every slot not listed.
+    pub fn with_ones(ones: &[usize]) -> Sudoku9x9 {
+        let cells: Vec<(usize, &[u8])> = (1..=81)
+            .filter(|id| !ones.contains(id))
+            .map(|id| (id, WITHOUT_ONE))
+            .collect();
+        with_candidates(&cells)
+    }
+
+    /// Check that there are deductions and that they never contradict the known solution.
+    pub fn assert_consistent(deductions: &[Deduction], solution_filename: &str) {
+        assert!(!deductions.is_empty(), "No deductions to check.");
+        let solution: Vec<u8> = load_csv(solution_filename)
+            .into_iter()
+            .flatten()
+            .map(|value| value.unwrap())
+            .collect();
+        for deduction in deductions {
+            for &(slot_id, value) in deduction.placements() {
+                assert_eq!(solution[slot_id - 1], value, "{deduction}");
+            }
+            for &(slot_id, value) in deduction.eliminations() {
+                assert_ne!(solution[slot_id - 1], value, "{deduction}");
+            }
+        }
+    }
+}
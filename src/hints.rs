@@ -0,0 +1,250 @@
+use crate::helpers::{cell_name, position};
+use crate::objects::deduction::Deduction;
+use crate::solver::default_strategies;
+use crate::strategies::backtracking::solutions;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use tracing::debug;
+
+/// How much a hint reveals, each level adds to the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HintLevel {
+    /// Only the technique to look for.
+    Technique,
+    /// The technique and the row, column or box to look at.
+    Unit,
+    /// The cells of the pattern and what they allow.
+    Result,
+}
+
+/// Next move on a grid, described at increasing levels of detail.
+#[derive(Debug, Clone)]
+pub struct Hint {
+    deduction: Deduction,
+    unit: String,
+    cells: Vec<String>,
+    results: Vec<String>,
+}
+
+impl Hint {
+    fn new<S: SimpleSudoku>(deduction: Deduction, sudoku: &S) -> Self {
+        let positions: Vec<(usize, usize, usize)> = deduction
+            .cells()
+            .iter()
+            .map(|&slot_id| position(&sudoku.slots()[slot_id - 1].borrow()))
+            .collect();
+        let shared = |unit: fn(&(usize, usize, usize)) -> usize| {
+            let first = unit(positions.first()?);
+            positions.iter().all(|p| unit(p) == first).then_some(first)
+        };
+        // A single cell is in all its units, the box gives away the least.
+        let unit = match (positions.len(), shared(|p| p.0), shared(|p| p.1)) {
+            (2.., Some(row), _) => format!("row {row}"),
+            (2.., None, Some(column)) => format!("column {column}"),
+            _ => {
+                let affected = deduction
+                    .placements()
+                    .iter()
+                    .chain(deduction.eliminations())
+                    .map(|&(slot_id, _)| slot_id);
+                let area = shared(|p| p.2).or_else(|| {
+                    let slot_id = deduction.cells().iter().copied().chain(affected).next()?;
+                    Some(position(&sudoku.slots()[slot_id - 1].borrow()).2)
+                });
+                area.map_or_else(|| "the grid".to_string(), |area| format!("box {area}"))
+            }
+        };
+
+        let cells = deduction
+            .cells()
+            .iter()
+            .map(|&slot_id| cell_name(sudoku, slot_id))
+            .collect();
+        let placements = deduction
+            .placements()
+            .iter()
+            .map(|&(slot_id, value)| format!("{}={value}", cell_name(sudoku, slot_id)));
+        let eliminations = deduction
+            .eliminations()
+            .iter()
+            .map(|&(slot_id, value)| format!("{}<>{value}", cell_name(sudoku, slot_id)));
+        let results = placements.chain(eliminations).collect();
+        Self {
+            deduction,
+            unit,
+            cells,
+            results,
+        }
+    }
+
+    pub fn technique(&self) -> &'static str {
+        self.deduction.technique()
+    }
+
+    /// Row, column or box to look at, e.g. "row 3".
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn deduction(&self) -> &Deduction {
+        &self.deduction
+    }
+
+    pub fn describe(&self, level: HintLevel) -> String {
+        match level {
+            HintLevel::Technique => format!("Look for a {}.", self.technique()),
+            HintLevel::Unit => format!("Look for a {} in {}.", self.technique(), self.unit),
+            HintLevel::Result => {
+                let mut text = format!("{} in {}", self.technique(), self.cells.join(" "));
+                if let Some(explanation) = self.deduction.explanation() {
+                    text.push_str(&format!(" ({explanation})"));
+                }
+                format!("{text}: {}.", self.results.join(", "))
+            }
+        }
+    }
+}
+
+/// Restrict the candidates of every slot to the player's pencil marks, indexed by
+/// `id - 1`; slots without marks are left as they are. Marks are trusted, wrong ones
+/// lead to wrong hints.
+pub fn apply_pencil_marks<S: SimpleSudoku>(sudoku: &S, marks: &[Vec<u8>]) {
+    for (slot, marks) in sudoku.slots().iter().zip(marks) {
+        if !marks.is_empty() {
+            slot.borrow_mut().keep_candidates(marks);
+        }
+    }
+}
+
+/// Finds the next move on a player's grid with the logical strategies, tried in order.
+pub struct Hinter<S: SimpleSudoku> {
+    strategies: Vec<Box<dyn Strategy<S>>>,
+    allow_backtracking: bool,
+    assume_unique: bool,
+}
+
+impl<S: SimpleSudoku> Hinter<S> {
+    pub fn new(strategies: Vec<Box<dyn Strategy<S>>>) -> Self {
+        Self {
+            strategies,
+            allow_backtracking: false,
+            assume_unique: false,
+        }
+    }
+
+    /// Hinter with the strategies of `Solver::with_defaults`.
+    pub fn with_defaults() -> Self {
+        Self::new(default_strategies())
+    }
+
+    /// When no strategy applies, reveal a value of the solution found by backtracking.
+    pub fn set_allow_backtracking(&mut self, allow_backtracking: bool) {
+        self.allow_backtracking = allow_backtracking;
+    }
+
+    /// Allow strategies that are only sound when the puzzle has a unique solution.
+    pub fn set_assume_unique(&mut self, assume_unique: bool) {
+        self.assume_unique = assume_unique;
+    }
+
+    /// Next move on the grid, which is left untouched. `None` when solved, or when
+    /// stuck and backtracking isn't allowed.
+    pub fn hint(&self, sudoku: &S) -> Option<Hint> {
+        for strategy in &self.strategies {
+            if strategy.requires_unique_solution() && !self.assume_unique {
+                continue;
+            }
+            if let Some(deduction) = strategy
+                .deductions(sudoku)
+                .into_iter()
+                .find(|deduction| deduction.changes(sudoku))
+            {
+                debug!("Hint: {}.", deduction);
+                return Some(Hint::new(deduction, sudoku));
+            }
+        }
+        if !self.allow_backtracking {
+            return None;
+        }
+
+        // The open slot with the fewest candidates is the easiest to check.
+        let solution = solutions(sudoku, 1).next()?;
+        let slot = sudoku
+            .slots()
+            .iter()
+            .filter(|slot| !slot.borrow().is_solved())
+            .min_by_key(|slot| slot.borrow().candidates().len())?;
+        let slot_id = slot.borrow().id();
+        let mut deduction = Deduction::new("Backtracking", vec![slot_id]);
+        deduction.place(slot_id, solution[slot_id - 1]);
+        debug!("Hint: {}.", deduction);
+        Some(Hint::new(deduction, sudoku))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::strategies::ConstraintPropagationStrategy;
+    use crate::traits::HasSlots;
+
+    #[test]
+    fn test_hint_levels() {
+        let sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let hint = Hinter::with_defaults()
+            .hint(&sudoku)
+            .expect("A single exists.");
+        assert!(hint.technique().ends_with("Single"));
+        assert_eq!(
+            hint.describe(HintLevel::Technique),
+            format!("Look for a {}.", hint.technique())
+        );
+        assert!(
+            hint.describe(HintLevel::Unit)
+                .ends_with(&format!(" in {}.", hint.unit()))
+        );
+        let result = hint.describe(HintLevel::Result);
+        let &(slot_id, value) = &hint.deduction().placements()[0];
+        assert!(result.contains(&format!("{}={value}", cell_name(&sudoku, slot_id))));
+        // The grid is left untouched.
+        assert!(!sudoku.slots()[slot_id - 1].borrow().is_solved());
+    }
+
+    #[test]
+    fn test_backtracking_only_when_allowed() {
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        ConstraintPropagationStrategy.run(&mut sudoku);
+        let mut hinter: Hinter<Sudoku9x9> =
+            Hinter::new(vec![Box::new(ConstraintPropagationStrategy)]);
+        assert!(hinter.hint(&sudoku).is_none());
+
+        hinter.set_allow_backtracking(true);
+        let hint = hinter.hint(&sudoku).expect("hard_1 has a solution.");
+        assert_eq!(hint.technique(), "Backtracking");
+        let solution = load_csv("fixtures/hard_1_solved.csv");
+        let &(slot_id, value) = &hint.deduction().placements()[0];
+        assert_eq!(solution[(slot_id - 1) / 9][(slot_id - 1) % 9], Some(value));
+    }
+
+    #[test]
+    fn test_pencil_marks() {
+        let sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let solution = load_csv("fixtures/hard_1_solved.csv");
+        let first_open = sudoku
+            .slots()
+            .iter()
+            .position(|slot| !slot.borrow().is_solved())
+            .expect("The puzzle isn't solved.");
+        let mut marks = vec![Vec::new(); 81];
+        marks[first_open] = vec![solution[first_open / 9][first_open % 9].unwrap()];
+        apply_pencil_marks(&sudoku, &marks);
+
+        let hint = Hinter::with_defaults()
+            .hint(&sudoku)
+            .expect("A single exists.");
+        assert_eq!(hint.technique(), "Naked Single");
+        assert_eq!(hint.deduction().cells(), &[first_open + 1]);
+        assert!(hint.unit().starts_with("box "));
+    }
+}
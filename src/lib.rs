@@ -1,6 +1,11 @@
 mod helpers;
+pub mod hints;
 pub mod objects;
+pub mod observer;
 pub mod parsers;
+pub mod rating;
+pub mod registry;
 pub mod solver;
 pub mod strategies;
 pub mod traits;
+pub mod walkthrough;
@@ -1,12 +1,9 @@
 use sudoku_solver::objects::sudoku::Sudoku9x9;
 use sudoku_solver::parsers::{load_csv, load_json};
+use sudoku_solver::registry::{StrategyRegistry, StrategySelection};
 use sudoku_solver::solver::Solver;
-use sudoku_solver::strategies::{
-    BacktrackingStrategy, ConstraintPropagationStrategy, HiddenSingleInColumnStrategy,
-    HiddenSingleInRowStrategy,
-};
 use sudoku_solver::traits::SimpleSudoku;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 use tracing_subscriber::fmt::format;
 
 fn setup_tracing() {
@@ -28,6 +25,15 @@ fn setup_tracing() {
     debug!("Tracing is set up.");
 }
 
+/// Strategy spec from `--strategies <spec>`, else from `SUDOKU_STRATEGIES` (see
+/// `example.env`), else the expert preset. See `StrategySelection::parse`.
+fn strategy_spec() -> String {
+    let mut args = std::env::args().skip_while(|arg| arg != "--strategies");
+    args.nth(1)
+        .or_else(|| std::env::var("SUDOKU_STRATEGIES").ok())
+        .unwrap_or_else(|| "expert".to_string())
+}
+
 fn main() {
     dotenv::dotenv().ok();
     setup_tracing();
@@ -51,11 +57,17 @@ fn main() {
     // sudoku.display_area_ids();
 
     // Full setup usage for Solver.
+    let spec = strategy_spec();
+    info!("Strategies: {spec}.");
     let mut solver = Solver::new(&mut sudoku);
-    solver.add_strategy(Box::new(ConstraintPropagationStrategy));
-    solver.add_strategy(Box::new(HiddenSingleInRowStrategy));
-    solver.add_strategy(Box::new(HiddenSingleInColumnStrategy));
-    solver.set_backtracking_strategy(Box::new(BacktrackingStrategy));
+    if let Err(err) =
+        StrategyRegistry::with_builtins().configure(&mut solver, &StrategySelection::parse(&spec))
+    {
+        error!("{err}");
+        return;
+    }
+    // Fixtures have a unique solution, so uniqueness strategies are sound here.
+    solver.set_assume_unique(true);
     solver.solve();
 
     // Simple usage of Solver.
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Flag shared with a running solve, e.g. from a request handler, to stop it early.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Why work stopped before completion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interruption {
+    /// The time or node budget ran out.
+    TimedOut,
+    /// The cancellation token was triggered.
+    Cancelled,
+}
+
+/// Limits on a solve: a deadline, a number of search nodes and a cancellation token.
+/// Clones share the node counter, so a budget can be spent from several threads.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    max_nodes: Option<usize>,
+    nodes: Arc<AtomicUsize>,
+    token: Option<CancellationToken>,
+}
+
+impl Budget {
+    /// Budget without any limit.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stop once `limit` has elapsed, counted from now.
+    pub fn with_time_limit(self, limit: Duration) -> Self {
+        self.with_deadline(Instant::now() + limit)
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop once searches have visited more than `max_nodes` nodes in total.
+    pub fn with_node_limit(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Record search nodes visited.
+    pub fn spend(&self, nodes: usize) {
+        self.nodes.fetch_add(nodes, Ordering::Relaxed);
+    }
+
+    /// Search nodes visited so far.
+    pub fn nodes(&self) -> usize {
+        self.nodes.load(Ordering::Relaxed)
+    }
+
+    /// Why work has to stop, `None` while the budget allows it to go on.
+    pub fn check(&self) -> Option<Interruption> {
+        if self
+            .token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Some(Interruption::Cancelled);
+        }
+        let out_of_time = self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline);
+        let out_of_nodes = self.max_nodes.is_some_and(|max| self.nodes() > max);
+        (out_of_time || out_of_nodes).then_some(Interruption::TimedOut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget() {
+        assert_eq!(Budget::unlimited().check(), None);
+
+        let budget = Budget::unlimited().with_node_limit(10);
+        budget.clone().spend(10);
+        assert_eq!(budget.check(), None);
+        budget.spend(1);
+        assert_eq!(budget.check(), Some(Interruption::TimedOut));
+
+        let budget = Budget::unlimited().with_time_limit(Duration::ZERO);
+        assert_eq!(budget.check(), Some(Interruption::TimedOut));
+
+        let token = CancellationToken::new();
+        let budget = Budget::unlimited()
+            .with_time_limit(Duration::ZERO)
+            .with_cancellation(token.clone());
+        token.cancel();
+        assert_eq!(budget.check(), Some(Interruption::Cancelled));
+    }
+}
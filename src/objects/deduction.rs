@@ -0,0 +1,131 @@
+use crate::traits::SimpleSudoku;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A single logical step found by a strategy: the cells forming the pattern,
+/// and the placements / eliminations it allows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deduction {
+    technique: &'static str,
+    cells: Vec<usize>,
+    placements: Vec<(usize, u8)>,
+    eliminations: Vec<(usize, u8)>,
+    explanation: Option<String>,
+}
+
+impl Deduction {
+    pub fn new(technique: &'static str, cells: Vec<usize>) -> Self {
+        Deduction {
+            technique,
+            cells,
+            placements: Vec::new(),
+            eliminations: Vec::new(),
+            explanation: None,
+        }
+    }
+
+    pub fn place(&mut self, slot_id: usize, value: u8) {
+        if !self.placements.contains(&(slot_id, value)) {
+            self.placements.push((slot_id, value));
+        }
+    }
+
+    pub fn eliminate(&mut self, slot_id: usize, value: u8) {
+        if !self.eliminations.contains(&(slot_id, value)) {
+            self.eliminations.push((slot_id, value));
+        }
+    }
+
+    /// Human-readable description of the pattern, e.g. a chain in Eureka notation.
+    pub fn set_explanation(&mut self, explanation: String) {
+        self.explanation = Some(explanation);
+    }
+
+    pub fn technique(&self) -> &'static str {
+        self.technique
+    }
+
+    /// Ids of the slots that form the pattern.
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    pub fn placements(&self) -> &[(usize, u8)] {
+        &self.placements
+    }
+
+    pub fn eliminations(&self) -> &[(usize, u8)] {
+        &self.eliminations
+    }
+
+    pub fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.placements.is_empty() && self.eliminations.is_empty()
+    }
+
+    /// Whether applying the deduction would change the grid, judged on live candidates.
+    pub fn changes<S: SimpleSudoku>(&self, sudoku: &S) -> bool {
+        let slot = |slot_id: usize| sudoku.slots()[slot_id - 1].borrow();
+        self.placements
+            .iter()
+            .any(|&(slot_id, _)| !slot(slot_id).is_solved())
+            || self.eliminations.iter().any(|&(slot_id, value)| {
+                let slot = slot(slot_id);
+                !slot.is_solved() && slot.candidates().contains(&value)
+            })
+    }
+
+    /// Apply the deduction to the sudoku, returns `true` if anything changed.
+    pub fn apply<S: SimpleSudoku>(&self, sudoku: &S) -> bool {
+        let mut changed = false;
+        for &(slot_id, value) in &self.placements {
+            let mut slot = sudoku.slots()[slot_id - 1].borrow_mut();
+            changed |= slot.set_value(Some(value));
+        }
+        for &(slot_id, value) in &self.eliminations {
+            let mut slot = sudoku.slots()[slot_id - 1].borrow_mut();
+            if slot.has_candidate(value) {
+                changed |= slot.exclude_value(value);
+            }
+        }
+        changed
+    }
+}
+
+impl Display for Deduction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.technique, self.cells)?;
+        if let Some(explanation) = &self.explanation {
+            write!(f, " {explanation}")?;
+        }
+        if !self.placements.is_empty() {
+            let placements: Vec<String> = self
+                .placements
+                .iter()
+                .map(|(slot_id, value)| format!("{slot_id}={value}"))
+                .collect();
+            write!(f, " => {}", placements.join(", "))?;
+        }
+        if !self.eliminations.is_empty() {
+            let eliminations: Vec<String> = self
+                .eliminations
+                .iter()
+                .map(|(slot_id, value)| format!("{slot_id}<>{value}"))
+                .collect();
+            write!(f, " => {}", eliminations.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Apply all deductions, returns `true` if any of them changed the sudoku.
+pub fn apply_deductions<S: SimpleSudoku>(sudoku: &S, deductions: &[Deduction]) -> bool {
+    let mut changed = false;
+    for deduction in deductions {
+        changed |= deduction.apply(sudoku);
+    }
+    changed
+}
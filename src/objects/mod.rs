@@ -1,4 +1,6 @@
 pub mod area;
+pub mod budget;
+pub mod deduction;
 pub mod line;
 pub mod slot;
 pub mod sudoku;
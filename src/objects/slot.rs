@@ -11,6 +11,7 @@ use std::rc::{Rc, Weak};
 pub struct Slot {
     id: usize,
     value: Option<u8>,
+    given: bool,
     possible_variants: HashSet<u8>,
     restricted_variants: HashSet<u8>,
     row: Weak<RefCell<Line>>,
@@ -23,6 +24,7 @@ impl Slot {
         let mut slot = Slot {
             id,
             value: None,
+            given: value.is_some(),
             possible_variants: HashSet::new(),
             restricted_variants: HashSet::new(),
             row: Weak::new(),
@@ -72,6 +74,11 @@ impl Slot {
         self.value.is_some()
     }
 
+    /// Whether the value was given by the puzzle, not found while solving.
+    pub fn is_given(&self) -> bool {
+        self.given
+    }
+
     pub fn exclude_values<T: Candidate>(&mut self, values: T) -> bool {
         if self.is_solved() {
             return false;
@@ -114,6 +121,26 @@ impl Slot {
         self.possible_variants.len() != before
     }
 
+    /// Restrict the candidates to `values`, e.g. to a player's pencil marks. Unlike
+    /// `exclude_values`, a single candidate left is not placed.
+    pub fn keep_candidates(&mut self, values: &[u8]) -> bool {
+        if self.is_solved() {
+            return false;
+        }
+        let before = self.possible_variants.len();
+        let removed: Vec<u8> = self
+            .possible_variants
+            .iter()
+            .copied()
+            .filter(|value| !values.contains(value))
+            .collect();
+        for value in removed {
+            self.restricted_variants.insert(value);
+            self.possible_variants.remove(&value);
+        }
+        self.possible_variants.len() != before
+    }
+
     pub fn variants(&self) -> HashSet<u8> {
         if let Some(val) = self.value {
             return HashSet::from([val]);
@@ -190,6 +217,18 @@ impl Slot {
         values
     }
 
+    /// Candidates of the slot with the values of already solved peers filtered out.
+    pub fn candidates(&self) -> HashSet<u8> {
+        if let Some(val) = self.value {
+            return HashSet::from([val]);
+        }
+        let solved_peers_values = self.get_solved_peers_values();
+        self.possible_variants
+            .difference(&solved_peers_values)
+            .copied()
+            .collect()
+    }
+
     pub fn has_candidate(&self, value: u8) -> bool {
         self.possible_variants.contains(&value)
     }
@@ -253,6 +292,15 @@ mod tests {
         assert!(!slot.is_solved());
     }
 
+    #[test]
+    fn test_is_given() {
+        let mut slot = Slot::new(1, None);
+        slot.set_value(Some(3));
+
+        assert!(Slot::new(1, Some(3)).is_given());
+        assert!(!slot.is_given());
+    }
+
     #[test]
     fn test_get_value() {
         let fake_value = 2;
@@ -262,4 +310,15 @@ mod tests {
         let val = slot.get_value();
         assert_eq!(val, Some(fake_value));
     }
+
+    #[test]
+    fn test_keep_candidates() {
+        let mut slot = Slot::new(1, None);
+
+        assert!(slot.keep_candidates(&[4]));
+        assert!(!slot.is_solved());
+        assert_eq!(slot.variants(), HashSet::from([4]));
+        assert!(!slot.keep_candidates(&[4, 5]));
+        assert!(!Slot::new(1, Some(3)).keep_candidates(&[4]));
+    }
 }
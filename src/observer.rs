@@ -0,0 +1,115 @@
+use crate::solver::{GridSnapshot, Outcome};
+
+/// Callbacks of a solve, for progress bars, animations or metrics. Every method does
+/// nothing by default, so observers only implement the events they need.
+pub trait SolverObserver {
+    /// `Solver::solve` started on this grid.
+    fn solver_started(&mut self, _grid: &GridSnapshot) {}
+
+    fn solver_finished(&mut self, _outcome: &Outcome) {}
+
+    /// A strategy is about to run, named as by `Strategy::name`.
+    fn strategy_started(&mut self, _strategy: &str) {}
+
+    /// The strategy ran, `progress` is whether it changed the grid.
+    fn strategy_finished(&mut self, _strategy: &str, _progress: bool) {}
+
+    fn placed(&mut self, _slot_id: usize, _value: u8) {}
+
+    /// A candidate was removed from a slot that is still open.
+    fn eliminated(&mut self, _slot_id: usize, _value: u8) {}
+
+    /// The search tries `value` in the slot, `depth` guesses deep. Only the
+    /// backtracking and dancing links searches report guesses, the parallel and SAT
+    /// searches don't.
+    fn guessed(&mut self, _slot_id: usize, _value: u8, _depth: usize) {}
+
+    /// The search takes back a guess that led nowhere.
+    fn undone(&mut self, _slot_id: usize, _value: u8, _depth: usize) {}
+}
+
+/// Observer ignoring every event.
+impl SolverObserver for () {}
+
+/// An event of `SolverObserver`, as recorded by `EventLog`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolverEvent {
+    SolverStarted(GridSnapshot),
+    SolverFinished(Outcome),
+    StrategyStarted(String),
+    StrategyFinished {
+        strategy: String,
+        progress: bool,
+    },
+    Placed {
+        slot_id: usize,
+        value: u8,
+    },
+    Eliminated {
+        slot_id: usize,
+        value: u8,
+    },
+    Guessed {
+        slot_id: usize,
+        value: u8,
+        depth: usize,
+    },
+    Undone {
+        slot_id: usize,
+        value: u8,
+        depth: usize,
+    },
+}
+
+/// Observer keeping every event in order, e.g. to replay a solve.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    pub events: Vec<SolverEvent>,
+}
+
+impl SolverObserver for EventLog {
+    fn solver_started(&mut self, grid: &GridSnapshot) {
+        self.events.push(SolverEvent::SolverStarted(grid.clone()));
+    }
+
+    fn solver_finished(&mut self, outcome: &Outcome) {
+        self.events
+            .push(SolverEvent::SolverFinished(outcome.clone()));
+    }
+
+    fn strategy_started(&mut self, strategy: &str) {
+        self.events
+            .push(SolverEvent::StrategyStarted(strategy.to_string()));
+    }
+
+    fn strategy_finished(&mut self, strategy: &str, progress: bool) {
+        self.events.push(SolverEvent::StrategyFinished {
+            strategy: strategy.to_string(),
+            progress,
+        });
+    }
+
+    fn placed(&mut self, slot_id: usize, value: u8) {
+        self.events.push(SolverEvent::Placed { slot_id, value });
+    }
+
+    fn eliminated(&mut self, slot_id: usize, value: u8) {
+        self.events.push(SolverEvent::Eliminated { slot_id, value });
+    }
+
+    fn guessed(&mut self, slot_id: usize, value: u8, depth: usize) {
+        self.events.push(SolverEvent::Guessed {
+            slot_id,
+            value,
+            depth,
+        });
+    }
+
+    fn undone(&mut self, slot_id: usize, value: u8, depth: usize) {
+        self.events.push(SolverEvent::Undone {
+            slot_id,
+            value,
+            depth,
+        });
+    }
+}
@@ -0,0 +1,201 @@
+use crate::solver::Solver;
+use crate::traits::SimpleSudoku;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// Difficulty buckets, from the hardest technique needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Average,
+    Hard,
+    Expert,
+    Extreme,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Score of every use of a technique and the difficulty it implies, in the spirit of
+/// HoDoKu. Techniques missing here count as `UNKNOWN`.
+const TECHNIQUES: &[(&str, u32, Difficulty)] = &[
+    ("Naked Single", 4, Difficulty::Easy),
+    ("Hidden Single", 14, Difficulty::Average),
+    ("Skyscraper", 130, Difficulty::Hard),
+    ("2-String Kite", 150, Difficulty::Hard),
+    ("Turbot Fish", 120, Difficulty::Hard),
+    ("Empty Rectangle", 120, Difficulty::Hard),
+    ("XY-Wing", 160, Difficulty::Hard),
+    ("XYZ-Wing", 180, Difficulty::Hard),
+    ("W-Wing", 150, Difficulty::Hard),
+    ("Color Trap", 150, Difficulty::Hard),
+    ("Color Wrap", 150, Difficulty::Hard),
+    ("Multi-Colors", 200, Difficulty::Expert),
+    ("Sue de Coq", 250, Difficulty::Hard),
+    ("Unique Rectangle Type 1", 100, Difficulty::Hard),
+    ("Unique Rectangle Type 2", 100, Difficulty::Hard),
+    ("Unique Rectangle Type 3", 100, Difficulty::Hard),
+    ("Unique Rectangle Type 4", 100, Difficulty::Hard),
+    ("Unique Rectangle Type 5", 100, Difficulty::Hard),
+    ("Unique Rectangle Type 6", 100, Difficulty::Hard),
+    ("Avoidable Rectangle Type 1", 100, Difficulty::Hard),
+    ("Avoidable Rectangle Type 2", 100, Difficulty::Hard),
+    ("BUG+1", 100, Difficulty::Hard),
+    ("X-Chain", 260, Difficulty::Expert),
+    ("XY-Chain", 260, Difficulty::Expert),
+    ("Continuous Nice Loop", 280, Difficulty::Expert),
+    ("Discontinuous Nice Loop", 280, Difficulty::Expert),
+    ("AIC", 280, Difficulty::Expert),
+    ("ALS-XZ", 300, Difficulty::Expert),
+    ("ALS-XY-Wing", 320, Difficulty::Expert),
+    ("Death Blossom", 360, Difficulty::Extreme),
+    ("Nishio", 400, Difficulty::Extreme),
+    ("Cell Forcing Chain", 500, Difficulty::Extreme),
+    ("Unit Forcing Chain", 500, Difficulty::Extreme),
+    ("Digit Forcing Chain", 500, Difficulty::Extreme),
+    ("Pattern Overlay", 500, Difficulty::Extreme),
+    ("Junior Exocet", 600, Difficulty::Extreme),
+    ("Backtracking", 10_000, Difficulty::Extreme),
+];
+
+const UNKNOWN: (u32, Difficulty) = (500, Difficulty::Extreme);
+
+/// Score and difficulty level of a technique.
+pub fn technique_weight(technique: &str) -> (u32, Difficulty) {
+    TECHNIQUES
+        .iter()
+        .find(|(name, _, _)| *name == technique)
+        .map_or(UNKNOWN, |&(_, score, difficulty)| (score, difficulty))
+}
+
+/// Result of rating a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating {
+    /// Sum of the scores of every step.
+    pub score: u32,
+    /// Technique of the highest difficulty used, the higher scored one of a level,
+    /// `None` for an already solved grid.
+    pub hardest: Option<&'static str>,
+    /// Difficulty of the hardest technique. The score doesn't change it: many easy
+    /// steps make a long puzzle, not a hard one.
+    pub difficulty: Difficulty,
+    /// Number of uses of every technique.
+    pub techniques: BTreeMap<&'static str, usize>,
+}
+
+impl Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.difficulty, self.score)?;
+        if let Some(hardest) = self.hardest {
+            write!(f, ", hardest step: {hardest}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Rates puzzles by solving them step by step with the strategies of
+/// `Solver::with_defaults`, cheapest technique first. A puzzle the strategies can't
+/// finish counts one "Backtracking" step.
+#[derive(Debug, Default)]
+pub struct Rater {
+    /// Allow uniqueness strategies, for puzzles known to have a unique solution.
+    pub assume_unique: bool,
+}
+
+impl Rater {
+    /// Rate the puzzle, which is left as far as the strategies could solve it.
+    pub fn rate<S: SimpleSudoku + Display>(&self, sudoku: &mut S) -> Rating {
+        let mut solver = Solver::with_defaults(sudoku);
+        solver.set_assume_unique(self.assume_unique);
+        let mut techniques: Vec<&'static str> = solver
+            .steps()
+            .map(|deduction| deduction.technique())
+            .collect();
+        if !solver.sudoku().is_solved() {
+            techniques.push("Backtracking");
+        }
+
+        let mut rating = Rating {
+            score: 0,
+            hardest: None,
+            difficulty: Difficulty::Easy,
+            techniques: BTreeMap::new(),
+        };
+        let mut hardest = (Difficulty::Easy, 0);
+        for technique in techniques {
+            let (score, difficulty) = technique_weight(technique);
+            rating.score += score;
+            if (difficulty, score) > hardest {
+                hardest = (difficulty, score);
+                rating.hardest = Some(technique);
+            }
+            *rating.techniques.entry(technique).or_default() += 1;
+        }
+        rating.difficulty = hardest.0;
+        rating
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::registry::StrategyRegistry;
+    use crate::traits::Solvable;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::easy("fixtures/easy.csv", Difficulty::Easy, "Naked Single")]
+    #[case::average("fixtures/average.csv", Difficulty::Average, "Hidden Single")]
+    #[case::hard_1("fixtures/hard_1.csv", Difficulty::Hard, "Sue de Coq")]
+    // Labelled hard by hand, but singles alone solve it.
+    #[case::hard_2("fixtures/hard_2.csv", Difficulty::Average, "Hidden Single")]
+    fn test_fixture_ratings(
+        #[case] puzzle: &str,
+        #[case] difficulty: Difficulty,
+        #[case] hardest: &str,
+    ) {
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let rating = Rater::default().rate(&mut sudoku);
+        assert_eq!(rating.difficulty, difficulty, "{puzzle}: {rating}");
+        assert_eq!(rating.hardest, Some(hardest));
+        assert!(sudoku.is_solved());
+    }
+
+    #[test]
+    fn test_backtracking_step() {
+        // Rows 1 and 2 of the first band hold 2 and 4 crosswise, no logic decides it.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        for (row, column) in [(0, 0), (0, 8), (1, 0), (1, 8)] {
+            grid[row][column] = None;
+        }
+        let mut sudoku = Sudoku9x9::new(grid);
+        let rating = Rater::default().rate(&mut sudoku);
+        assert_eq!(rating.hardest, Some("Backtracking"));
+        assert_eq!(rating.difficulty, Difficulty::Extreme);
+        assert_eq!(rating.score, 10_000);
+        assert_eq!(technique_weight("Something New"), UNKNOWN);
+    }
+
+    #[test]
+    fn test_every_technique_has_a_weight() {
+        let registry = StrategyRegistry::<Sudoku9x9>::with_builtins();
+        let mut labels: Vec<&str> = vec!["Backtracking"];
+        for name in registry.names() {
+            let strategy = registry.create(name).expect("Registered name.");
+            labels.extend(strategy.techniques());
+        }
+        assert!(labels.contains(&"Unique Rectangle Type 5"));
+        for technique in &labels {
+            let weighted = TECHNIQUES.iter().any(|(name, _, _)| name == technique);
+            assert!(weighted, "{technique} has no weight");
+        }
+        for (technique, _, _) in TECHNIQUES {
+            assert!(labels.contains(technique), "{technique} is never used");
+        }
+    }
+}
@@ -0,0 +1,453 @@
+use crate::solver::Solver;
+use crate::strategies::{
+    AlmostLockedSetStrategy, AlsXyWingStrategy, AlsXzStrategy, AlternatingInferenceChainStrategy,
+    AvoidableRectangleStrategy, BacktrackingStrategy, BugPlusOneStrategy,
+    ConstraintPropagationStrategy, DancingLinksStrategy, DeathBlossomStrategy,
+    EmptyRectangleStrategy, ForcingChainStrategy, HiddenSingleInColumnStrategy,
+    HiddenSingleInRowStrategy, JuniorExocetStrategy, MultiColoringStrategy, NishioStrategy,
+    ParallelBacktrackingStrategy, PatternOverlayStrategy, SatStrategy, SimpleColoringStrategy,
+    SkyscraperStrategy, SueDeCoqStrategy, TurbotFishStrategy, TwoStringKiteStrategy,
+    UniqueRectangleStrategy, WWingStrategy, XChainStrategy, XYChainStrategy, XYWingStrategy,
+    XYZWingStrategy,
+};
+use crate::traits::{SimpleSudoku, Strategy};
+use std::fmt::Display;
+
+pub type Constructor<S> = fn() -> Box<dyn Strategy<S>>;
+
+/// How the solver uses a strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyKind {
+    /// Tried in order, one after the other, until none makes progress.
+    Logical,
+    /// Finishes the grid when the logical strategies are stuck, see
+    /// `Solver::set_backtracking_strategy`.
+    Search,
+}
+
+/// Named sets of strategies, from singles only to plain search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Naked and hidden singles.
+    Beginner,
+    /// Singles, single digit patterns, wings and simple coloring.
+    Intermediate,
+    /// Every strategy of `Solver::with_defaults`, backtracking included.
+    Expert,
+    /// Backtracking only.
+    BruteForce,
+}
+
+const BEGINNER: &[&str] = &["naked_single", "hidden_single_row", "hidden_single_column"];
+
+const INTERMEDIATE: &[&str] = &[
+    "skyscraper",
+    "two_string_kite",
+    "turbot_fish",
+    "empty_rectangle",
+    "xy_wing",
+    "xyz_wing",
+    "w_wing",
+    "simple_coloring",
+];
+
+const EXPERT: &[&str] = &[
+    "multi_coloring",
+    "sue_de_coq",
+    // Uniqueness strategies are skipped unless uniqueness is assumed.
+    "unique_rectangle",
+    "avoidable_rectangle",
+    "bug_plus_one",
+    "x_chain",
+    "xy_chain",
+    "aic",
+    // ALS-XZ, ALS-XY-Wing and Death Blossom sharing one set search.
+    "almost_locked_sets",
+    "nishio",
+    "forcing_chain",
+    "pattern_overlay",
+    "backtracking",
+];
+
+impl Preset {
+    pub const ALL: [Preset; 4] = [
+        Preset::Beginner,
+        Preset::Intermediate,
+        Preset::Expert,
+        Preset::BruteForce,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Beginner => "beginner",
+            Preset::Intermediate => "intermediate",
+            Preset::Expert => "expert",
+            Preset::BruteForce => "brute-force",
+        }
+    }
+
+    /// Preset called `name`, "brute_force" is accepted as well.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.replace('_', "-");
+        Self::ALL.into_iter().find(|preset| preset.name() == name)
+    }
+
+    /// Names of the preset's strategies, cheapest first.
+    pub fn strategies(self) -> Vec<&'static str> {
+        match self {
+            Preset::Beginner => BEGINNER.to_vec(),
+            Preset::Intermediate => [BEGINNER, INTERMEDIATE].concat(),
+            Preset::Expert => [BEGINNER, INTERMEDIATE, EXPERT].concat(),
+            Preset::BruteForce => vec!["backtracking"],
+        }
+    }
+
+    pub fn selection(self) -> StrategySelection {
+        StrategySelection {
+            names: self.strategies().into_iter().map(String::from).collect(),
+        }
+    }
+}
+
+impl Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Ordered strategy names to set up a solver with. Logical strategies run in this
+/// order; of the search strategies, the last one is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StrategySelection {
+    names: Vec<String>,
+}
+
+impl StrategySelection {
+    /// Selection from a comma separated spec, for config files and the command line:
+    /// a preset adds its strategies, a name enables that strategy at the end of the
+    /// list and `-name` disables it. E.g. "expert,-nishio,sat" or
+    /// "naked_single,hidden_single_row".
+    pub fn parse(spec: &str) -> Self {
+        let mut selection = Self::default();
+        for token in spec.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if let Some(name) = token.strip_prefix('-') {
+                selection.disable(name);
+            } else if let Some(preset) = Preset::from_name(token) {
+                for name in preset.strategies() {
+                    selection.enable(name);
+                }
+            } else {
+                selection.enable(token);
+            }
+        }
+        selection
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Enable the strategy last, moving it there if it's already enabled.
+    pub fn enable(&mut self, name: &str) {
+        self.disable(name);
+        self.names.push(name.to_string());
+    }
+
+    /// Enable the strategy right before `before`, or last if `before` isn't enabled.
+    pub fn enable_before(&mut self, name: &str, before: &str) {
+        self.disable(name);
+        let idx = self
+            .names
+            .iter()
+            .position(|enabled| enabled == before)
+            .unwrap_or(self.names.len());
+        self.names.insert(idx, name.to_string());
+    }
+
+    pub fn disable(&mut self, name: &str) {
+        self.names.retain(|enabled| enabled != name);
+    }
+}
+
+/// A name that isn't registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStrategy(pub String);
+
+impl Display for UnknownStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown strategy \"{}\".", self.0)
+    }
+}
+
+impl std::error::Error for UnknownStrategy {}
+
+/// Strategies built from a selection.
+pub struct Strategies<S: SimpleSudoku> {
+    pub logical: Vec<Box<dyn Strategy<S>>>,
+    pub search: Option<Box<dyn Strategy<S>>>,
+}
+
+/// Maps stable names, e.g. "hidden_single_row", to strategy constructors.
+pub struct StrategyRegistry<S: SimpleSudoku> {
+    entries: Vec<(&'static str, StrategyKind, Constructor<S>)>,
+}
+
+impl<S: SimpleSudoku> StrategyRegistry<S> {
+    /// Registry without any strategy.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Registry of every strategy of the crate, cheapest first.
+    pub fn with_builtins() -> Self {
+        use StrategyKind::{Logical, Search};
+        let mut registry = Self::new();
+        registry.register("naked_single", Logical, || {
+            Box::new(ConstraintPropagationStrategy)
+        });
+        registry.register("hidden_single_row", Logical, || {
+            Box::new(HiddenSingleInRowStrategy)
+        });
+        registry.register("hidden_single_column", Logical, || {
+            Box::new(HiddenSingleInColumnStrategy)
+        });
+        registry.register("skyscraper", Logical, || Box::new(SkyscraperStrategy));
+        registry.register("two_string_kite", Logical, || {
+            Box::new(TwoStringKiteStrategy)
+        });
+        registry.register("turbot_fish", Logical, || Box::new(TurbotFishStrategy));
+        registry.register("empty_rectangle", Logical, || {
+            Box::new(EmptyRectangleStrategy)
+        });
+        registry.register("xy_wing", Logical, || Box::new(XYWingStrategy));
+        registry.register("xyz_wing", Logical, || Box::new(XYZWingStrategy));
+        registry.register("w_wing", Logical, || Box::new(WWingStrategy));
+        registry.register("simple_coloring", Logical, || {
+            Box::new(SimpleColoringStrategy)
+        });
+        registry.register("multi_coloring", Logical, || {
+            Box::new(MultiColoringStrategy)
+        });
+        registry.register("sue_de_coq", Logical, || Box::new(SueDeCoqStrategy));
+        registry.register("unique_rectangle", Logical, || {
+            Box::new(UniqueRectangleStrategy)
+        });
+        registry.register("avoidable_rectangle", Logical, || {
+            Box::new(AvoidableRectangleStrategy)
+        });
+        registry.register("bug_plus_one", Logical, || Box::new(BugPlusOneStrategy));
+        registry.register("x_chain", Logical, || Box::new(XChainStrategy::default()));
+        registry.register("xy_chain", Logical, || Box::new(XYChainStrategy::default()));
+        registry.register("aic", Logical, || {
+            Box::new(AlternatingInferenceChainStrategy::default())
+        });
+        registry.register("als_xz", Logical, || Box::new(AlsXzStrategy));
+        registry.register("als_xy_wing", Logical, || Box::new(AlsXyWingStrategy));
+        registry.register("death_blossom", Logical, || {
+            Box::new(DeathBlossomStrategy::default())
+        });
+        registry.register("almost_locked_sets", Logical, || {
+            Box::new(AlmostLockedSetStrategy::default())
+        });
+        registry.register("nishio", Logical, || Box::new(NishioStrategy::default()));
+        registry.register("forcing_chain", Logical, || {
+            Box::new(ForcingChainStrategy::default())
+        });
+        registry.register("pattern_overlay", Logical, || {
+            Box::new(PatternOverlayStrategy::default())
+        });
+        registry.register("junior_exocet", Logical, || Box::new(JuniorExocetStrategy));
+        registry.register("backtracking", Search, || {
+            Box::new(BacktrackingStrategy::default())
+        });
+        registry.register("parallel_backtracking", Search, || {
+            Box::new(ParallelBacktrackingStrategy::default())
+        });
+        registry.register("dancing_links", Search, || Box::new(DancingLinksStrategy));
+        registry.register("sat", Search, || Box::new(SatStrategy::default()));
+        registry
+    }
+
+    /// Register a strategy, replacing the one already registered under `name`.
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        kind: StrategyKind,
+        constructor: Constructor<S>,
+    ) {
+        match self.entries.iter_mut().find(|(known, _, _)| *known == name) {
+            Some(entry) => *entry = (name, kind, constructor),
+            None => self.entries.push((name, kind, constructor)),
+        }
+    }
+
+    /// Registered names, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|&(name, _, _)| name)
+    }
+
+    pub fn kind(&self, name: &str) -> Option<StrategyKind> {
+        self.entries
+            .iter()
+            .find(|(known, _, _)| *known == name)
+            .map(|&(_, kind, _)| kind)
+    }
+
+    pub fn create(&self, name: &str) -> Result<Box<dyn Strategy<S>>, UnknownStrategy> {
+        self.entries
+            .iter()
+            .find(|(known, _, _)| *known == name)
+            .map(|(_, _, constructor)| constructor())
+            .ok_or_else(|| UnknownStrategy(name.to_string()))
+    }
+
+    /// Strategies of the selection, failing on the first unknown name.
+    pub fn build(&self, selection: &StrategySelection) -> Result<Strategies<S>, UnknownStrategy> {
+        let mut strategies = Strategies {
+            logical: Vec::new(),
+            search: None,
+        };
+        for name in selection.names() {
+            let strategy = self.create(name)?;
+            match self.kind(name) {
+                Some(StrategyKind::Search) => strategies.search = Some(strategy),
+                _ => strategies.logical.push(strategy),
+            }
+        }
+        Ok(strategies)
+    }
+
+    /// Add the selected strategies to the solver, after the ones it already has.
+    pub fn configure(
+        &self,
+        solver: &mut Solver<'_, S>,
+        selection: &StrategySelection,
+    ) -> Result<(), UnknownStrategy>
+    where
+        S: Display,
+    {
+        let strategies = self.build(selection)?;
+        for strategy in strategies.logical {
+            solver.add_strategy(strategy);
+        }
+        if let Some(search) = strategies.search {
+            solver.set_backtracking_strategy(search);
+        }
+        Ok(())
+    }
+}
+
+impl<S: SimpleSudoku> Default for StrategyRegistry<S> {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::{assert_consistent, singles_solved};
+    use crate::objects::deduction::Deduction;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::solver::Outcome;
+    use rstest::rstest;
+
+    #[test]
+    fn test_builtins() {
+        let registry: StrategyRegistry<Sudoku9x9> = StrategyRegistry::with_builtins();
+        let names: Vec<&str> = registry.names().collect();
+        for (idx, name) in names.iter().enumerate() {
+            assert!(!names[..idx].contains(name), "{name} is registered twice.");
+            assert!(registry.create(name).is_ok());
+        }
+        for preset in Preset::ALL {
+            assert_eq!(Preset::from_name(preset.name()), Some(preset));
+            assert!(preset.strategies().iter().all(|name| names.contains(name)));
+        }
+        assert_eq!(Preset::from_name("brute_force"), Some(Preset::BruteForce));
+        assert_eq!(
+            registry.create("naked_pair").err(),
+            Some(UnknownStrategy("naked_pair".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_selection() {
+        let selection = StrategySelection::parse("beginner, -hidden_single_row, naked_single");
+        assert_eq!(
+            selection.names(),
+            &[
+                "hidden_single_column".to_string(),
+                "naked_single".to_string()
+            ]
+        );
+
+        let mut selection = Preset::Expert.selection();
+        selection.enable_before("junior_exocet", "x_chain");
+        let names = selection.names();
+        let position = |name: &str| names.iter().position(|enabled| enabled == name);
+        assert_eq!(
+            position("junior_exocet").unwrap() + 1,
+            position("x_chain").unwrap()
+        );
+
+        let registry: StrategyRegistry<Sudoku9x9> = StrategyRegistry::default();
+        let strategies = registry.build(&selection).unwrap();
+        assert_eq!(strategies.logical.len(), names.len() - 1);
+        assert!(strategies.search.is_some());
+        let strategies = registry
+            .build(&StrategySelection::parse("expert,-backtracking"))
+            .unwrap();
+        assert!(strategies.search.is_none());
+        assert!(
+            registry
+                .build(&StrategySelection::parse("beginner,naked_pair"))
+                .is_err()
+        );
+    }
+
+    #[rstest]
+    #[case::beginner_easy("beginner", "fixtures/easy.csv", true)]
+    #[case::beginner_hard("beginner", "fixtures/hard_1.csv", false)]
+    #[case::expert("expert", "fixtures/hard_1.csv", true)]
+    #[case::brute_force("brute-force", "fixtures/hard_1.csv", true)]
+    #[case::sat("naked_single,sat", "fixtures/hard_1.csv", true)]
+    fn test_presets_solve(#[case] spec: &str, #[case] puzzle: &str, #[case] solved: bool) {
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let mut solver = Solver::new(&mut sudoku);
+        StrategyRegistry::with_builtins()
+            .configure(&mut solver, &StrategySelection::parse(spec))
+            .unwrap();
+        let outcome = solver.solve();
+        assert_eq!(outcome.is_solved(), solved, "{spec} on {puzzle}");
+        if !solved {
+            assert!(matches!(outcome, Outcome::Stuck(_)));
+        }
+    }
+
+    #[test]
+    fn test_logical_strategies_are_consistent_with_solution() {
+        // hard_2 is solved by singles alone, only hard_1 leaves something to deduce.
+        let sudoku = singles_solved("fixtures/hard_1.csv");
+        let registry: StrategyRegistry<Sudoku9x9> = StrategyRegistry::with_builtins();
+        let mut deductions: Vec<Deduction> = Vec::new();
+        for name in registry.names() {
+            if registry.kind(name) != Some(StrategyKind::Logical) {
+                continue;
+            }
+            let strategy = registry.create(name).unwrap();
+            for deduction in strategy.deductions(&sudoku) {
+                assert!(
+                    strategy.techniques().contains(&deduction.technique()),
+                    "{name} doesn't list {}",
+                    deduction.technique()
+                );
+                deductions.push(deduction);
+            }
+        }
+        assert_consistent(&deductions, "fixtures/hard_1_solved.csv");
+    }
+}
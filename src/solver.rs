@@ -1,16 +1,83 @@
-use crate::strategies::{
-    BacktrackingStrategy, ConstraintPropagationStrategy, HiddenSingleInColumnStrategy,
-    HiddenSingleInRowStrategy,
-};
-use crate::traits::{SimpleSudoku, Strategy};
+use crate::objects::budget::{Budget, Interruption};
+use crate::objects::deduction::Deduction;
+use crate::observer::SolverObserver;
+use crate::registry::{Preset, StrategyRegistry, StrategySelection, UnknownStrategy};
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
 use chrono::Utc;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::Display;
-use tracing::{info, warn};
+use std::rc::Rc;
+use tracing::{debug, info, warn};
+
+/// Logical strategies of `Solver::with_defaults`, cheapest first.
+pub fn default_strategies<S: SimpleSudoku>() -> Vec<Box<dyn Strategy<S>>> {
+    StrategyRegistry::with_builtins()
+        .build(&Preset::Expert.selection())
+        .expect("Presets only name built-in strategies.")
+        .logical
+}
+
+/// Values and candidates of every slot, indexed by `id - 1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridSnapshot {
+    pub values: Vec<Option<u8>>,
+    /// Sorted candidates, empty for solved slots.
+    pub candidates: Vec<Vec<u8>>,
+}
+
+impl GridSnapshot {
+    pub fn of<S: SimpleSudoku>(sudoku: &S) -> Self {
+        let mut values = vec![None; sudoku.slots().len()];
+        let mut candidates = vec![Vec::new(); sudoku.slots().len()];
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            let idx = slot.id() - 1;
+            values[idx] = slot.get_value();
+            if values[idx].is_none() {
+                candidates[idx] = slot.candidates().into_iter().collect();
+                candidates[idx].sort_unstable();
+            }
+        }
+        Self { values, candidates }
+    }
+}
+
+/// How a solve ended; unfinished outcomes carry the grid as it was left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved,
+    /// No strategy made progress and backtracking, if set, found no solution.
+    Stuck(GridSnapshot),
+    /// The time or node budget ran out.
+    TimedOut(GridSnapshot),
+    Cancelled(GridSnapshot),
+}
+
+impl Outcome {
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Outcome::Solved)
+    }
+}
+
+/// Result of `Solver::solve_with_techniques`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TechniqueReport {
+    /// `Stuck` with the grid and its candidates when the allowed techniques run out.
+    pub outcome: Outcome,
+    /// Deductions applied, in order.
+    pub steps: Vec<Deduction>,
+    /// Techniques outside the allow-list that apply to the stuck grid, cheapest first.
+    pub needed: Vec<&'static str>,
+}
 
 pub struct Solver<'a, S: SimpleSudoku> {
     sudoku: &'a mut S,
     strategies: Vec<Box<dyn Strategy<S>>>,
     backtracking_strategy: Option<Box<dyn Strategy<S>>>,
+    assume_unique: bool,
+    budget: Budget,
+    observers: Vec<Rc<RefCell<dyn SolverObserver>>>,
 }
 
 impl<'a, S> Solver<'a, S>
@@ -22,6 +89,9 @@ where
             sudoku,
             strategies: vec![],
             backtracking_strategy: None,
+            assume_unique: false,
+            budget: Budget::unlimited(),
+            observers: Vec::new(),
         }
     }
 
@@ -33,12 +103,59 @@ where
         self.backtracking_strategy = Some(strategy);
     }
 
+    /// Allow strategies that are only sound when the puzzle has a unique solution.
+    pub fn set_assume_unique(&mut self, assume_unique: bool) {
+        self.assume_unique = assume_unique;
+    }
+
+    pub fn sudoku(&self) -> &S {
+        self.sudoku
+    }
+
+    /// Deadline, node limit and cancellation token for the next solves.
+    pub fn set_budget(&mut self, budget: Budget) {
+        self.budget = budget;
+    }
+
+    /// Notify the observer of the events of the next solves and steps. Keep a clone
+    /// of the `Rc` to read it afterwards.
+    pub fn add_observer(&mut self, observer: Rc<RefCell<dyn SolverObserver>>) {
+        self.observers.push(observer);
+    }
+
+    /// Run a strategy, reporting it and what it changed to the observers.
+    fn run_strategy(
+        sudoku: &mut S,
+        strategy: &dyn Strategy<S>,
+        budget: &Budget,
+        observers: &[Rc<RefCell<dyn SolverObserver>>],
+    ) {
+        if observers.is_empty() {
+            strategy.run_within(sudoku, budget);
+            return;
+        }
+        let mut observers = Broadcast(observers);
+        observers.strategy_started(strategy.name());
+        let before = marks(sudoku);
+        strategy.run_observed(sudoku, budget, &mut observers);
+        let progress = report_changes(&before, sudoku, &mut observers);
+        observers.strategy_finished(strategy.name(), progress);
+    }
+
+    /// Snapshot of solved slots and remaining candidates, changes on any progress.
+    fn progress(&self) -> (usize, usize) {
+        (
+            self.sudoku.count_solved_slots(),
+            self.sudoku.count_candidates(),
+        )
+    }
+
     fn print_is_solved(&self) {
         let solved_text = if self.sudoku.is_solved() { "Yes" } else { "No" };
         info!("Is solved now: {}", solved_text);
     }
 
-    pub fn solve(&mut self) {
+    pub fn solve(&mut self) -> Outcome {
         info!(
             "Sudoku has {} of {} solved slots ({:.3}%).",
             self.sudoku.count_solved_slots(),
@@ -48,30 +165,55 @@ where
         self.print_is_solved();
         info!("{:#}", self.sudoku);
         info!("Solver started.");
+        if !self.observers.is_empty() {
+            Broadcast(&self.observers).solver_started(&GridSnapshot::of(self.sudoku));
+        }
         let start_ts = Utc::now();
+        let mut interruption = None;
         'main: loop {
-            let before_solved_count = self.sudoku.count_solved_slots();
+            let before_progress = self.progress();
 
             for strategy in &self.strategies {
-                let before_strategy = self.sudoku.count_solved_slots();
-                strategy.run(self.sudoku);
+                if strategy.requires_unique_solution() && !self.assume_unique {
+                    continue;
+                }
+                interruption = self.budget.check();
+                if interruption.is_some() {
+                    break 'main;
+                }
+                let before_strategy = self.progress();
+                Self::run_strategy(
+                    self.sudoku,
+                    strategy.as_ref(),
+                    &self.budget,
+                    &self.observers,
+                );
 
                 if self.sudoku.is_solved() {
                     break 'main;
                 }
 
-                if self.sudoku.count_solved_slots() > before_strategy {
+                if self.progress() != before_strategy {
                     continue 'main;
                 }
             }
 
-            if self.sudoku.count_solved_slots() == before_solved_count {
+            if self.progress() == before_progress {
                 // No progress made with regular strategies
                 if !self.sudoku.is_solved()
                     && let Some(backtracking) = &self.backtracking_strategy
                 {
-                    warn!("No progress with regular strategies, attempting backtracking.");
-                    backtracking.run(self.sudoku);
+                    interruption = self.budget.check();
+                    if interruption.is_none() {
+                        warn!("No progress with regular strategies, attempting backtracking.");
+                        Self::run_strategy(
+                            self.sudoku,
+                            backtracking.as_ref(),
+                            &self.budget,
+                            &self.observers,
+                        );
+                        interruption = self.budget.check();
+                    }
                 }
 
                 break 'main;
@@ -89,14 +231,110 @@ where
         );
         self.print_is_solved();
         info!("{:#}", self.sudoku);
+
+        let outcome = self.outcome(interruption);
+        Broadcast(&self.observers).solver_finished(&outcome);
+        outcome
+    }
+
+    fn outcome(&self, interruption: Option<Interruption>) -> Outcome {
+        if self.sudoku.is_solved() {
+            return Outcome::Solved;
+        }
+        let snapshot = GridSnapshot::of(self.sudoku);
+        match interruption {
+            Some(Interruption::TimedOut) => Outcome::TimedOut(snapshot),
+            Some(Interruption::Cancelled) => Outcome::Cancelled(snapshot),
+            None => Outcome::Stuck(snapshot),
+        }
+    }
+
+    /// Apply the next deduction, found by the first strategy that has one in the order
+    /// strategies were added (cheapest first in `with_defaults`). Backtracking is never
+    /// used, `None` once the grid is solved, stuck or out of budget. Observers are told
+    /// the placements and eliminations, not the strategies tried.
+    pub fn step(&mut self) -> Option<Deduction> {
+        if self.sudoku.is_solved() || self.budget.check().is_some() {
+            return None;
+        }
+        let before = (!self.observers.is_empty()).then(|| marks(self.sudoku));
+        for strategy in &self.strategies {
+            if strategy.requires_unique_solution() && !self.assume_unique {
+                continue;
+            }
+            for deduction in strategy.deductions(self.sudoku) {
+                if deduction.apply(self.sudoku) {
+                    debug!("Step: {}.", deduction);
+                    if let Some(before) = before {
+                        report_changes(&before, self.sudoku, &mut Broadcast(&self.observers));
+                    }
+                    return Some(deduction);
+                }
+            }
+        }
+        None
+    }
+
+    /// Solve using only the logical strategies of the selection, given by registry name
+    /// or preset (e.g. "naked_single,hidden_single_row"), never backtracking. When stuck,
+    /// the report lists the techniques of the other expert strategies that apply to the
+    /// grid, cheapest first, whatever strategies the solver was built with. Fails on the
+    /// first name the registry doesn't know.
+    pub fn solve_with_techniques(
+        &mut self,
+        selection: &StrategySelection,
+    ) -> Result<TechniqueReport, UnknownStrategy> {
+        let registry = StrategyRegistry::with_builtins();
+        let allowed = registry.build(selection)?.logical;
+        let own = std::mem::replace(&mut self.strategies, allowed);
+        let steps: Vec<Deduction> = self.steps().collect();
+        self.strategies = own;
+
+        let outcome = self.outcome(self.budget.check());
+
+        let mut needed: Vec<&'static str> = Vec::new();
+        if matches!(outcome, Outcome::Stuck(_)) {
+            let mut others = Preset::Expert.selection();
+            for name in selection.names() {
+                others.disable(name);
+            }
+            let others = registry
+                .build(&others)
+                .expect("Presets only name built-in strategies.")
+                .logical;
+            for strategy in &others {
+                if strategy.requires_unique_solution() && !self.assume_unique {
+                    continue;
+                }
+                for deduction in strategy.deductions(self.sudoku) {
+                    if deduction.changes(self.sudoku) && !needed.contains(&deduction.technique()) {
+                        needed.push(deduction.technique());
+                    }
+                }
+            }
+        }
+        info!(
+            "Solved with allowed techniques in {} steps, techniques needed next: {:?}.",
+            steps.len(),
+            needed
+        );
+        Ok(TechniqueReport {
+            outcome,
+            steps,
+            needed,
+        })
+    }
+
+    /// Iterator applying one `step` per item.
+    pub fn steps(&mut self) -> Steps<'_, 'a, S> {
+        Steps { solver: self }
     }
 
     pub fn with_defaults(sudoku: &'a mut S) -> Self {
         let mut solver = Self::new(sudoku);
-        solver.add_strategy(Box::new(ConstraintPropagationStrategy));
-        solver.add_strategy(Box::new(HiddenSingleInRowStrategy));
-        solver.add_strategy(Box::new(HiddenSingleInColumnStrategy));
-        solver.set_backtracking_strategy(Box::new(BacktrackingStrategy));
+        StrategyRegistry::with_builtins()
+            .configure(&mut solver, &Preset::Expert.selection())
+            .expect("Presets only name built-in strategies.");
         solver
     }
 
@@ -105,3 +343,357 @@ where
         solver.solve();
     }
 }
+
+/// Value, or raw candidates when open, of every slot, indexed by `id - 1`.
+fn marks<S: SimpleSudoku>(sudoku: &S) -> Vec<(Option<u8>, HashSet<u8>)> {
+    sudoku
+        .slots()
+        .iter()
+        .map(|slot| {
+            let slot = slot.borrow();
+            (slot.get_value(), slot.variants())
+        })
+        .collect()
+}
+
+/// Report the placements and eliminations since `before`; returns whether there are any.
+fn report_changes<S: SimpleSudoku>(
+    before: &[(Option<u8>, HashSet<u8>)],
+    sudoku: &S,
+    observer: &mut dyn SolverObserver,
+) -> bool {
+    let mut changed = false;
+    let after = marks(sudoku);
+    for (idx, ((old_value, old_variants), (value, variants))) in
+        before.iter().zip(&after).enumerate()
+    {
+        if old_value == value && old_variants == variants {
+            continue;
+        }
+        changed = true;
+        match (old_value, value) {
+            (None, Some(value)) => observer.placed(idx + 1, *value),
+            (None, None) => {
+                let mut eliminated: Vec<u8> = old_variants.difference(variants).copied().collect();
+                eliminated.sort_unstable();
+                for value in eliminated {
+                    observer.eliminated(idx + 1, value);
+                }
+            }
+            // Placed values are never taken back.
+            (Some(_), _) => {}
+        }
+    }
+    changed
+}
+
+/// Forwards every event to all observers, in order.
+struct Broadcast<'o>(&'o [Rc<RefCell<dyn SolverObserver>>]);
+
+impl SolverObserver for Broadcast<'_> {
+    fn solver_started(&mut self, grid: &GridSnapshot) {
+        for observer in self.0 {
+            observer.borrow_mut().solver_started(grid);
+        }
+    }
+
+    fn solver_finished(&mut self, outcome: &Outcome) {
+        for observer in self.0 {
+            observer.borrow_mut().solver_finished(outcome);
+        }
+    }
+
+    fn strategy_started(&mut self, strategy: &str) {
+        for observer in self.0 {
+            observer.borrow_mut().strategy_started(strategy);
+        }
+    }
+
+    fn strategy_finished(&mut self, strategy: &str, progress: bool) {
+        for observer in self.0 {
+            observer.borrow_mut().strategy_finished(strategy, progress);
+        }
+    }
+
+    fn placed(&mut self, slot_id: usize, value: u8) {
+        for observer in self.0 {
+            observer.borrow_mut().placed(slot_id, value);
+        }
+    }
+
+    fn eliminated(&mut self, slot_id: usize, value: u8) {
+        for observer in self.0 {
+            observer.borrow_mut().eliminated(slot_id, value);
+        }
+    }
+
+    fn guessed(&mut self, slot_id: usize, value: u8, depth: usize) {
+        for observer in self.0 {
+            observer.borrow_mut().guessed(slot_id, value, depth);
+        }
+    }
+
+    fn undone(&mut self, slot_id: usize, value: u8, depth: usize) {
+        for observer in self.0 {
+            observer.borrow_mut().undone(slot_id, value, depth);
+        }
+    }
+}
+
+/// Deductions applied one at a time, see `Solver::step`.
+pub struct Steps<'s, 'a, S: SimpleSudoku> {
+    solver: &'s mut Solver<'a, S>,
+}
+
+impl<S> Iterator for Steps<'_, '_, S>
+where
+    S: SimpleSudoku + Display,
+{
+    type Item = Deduction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.solver.step()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::singles_solved;
+    use crate::objects::budget::CancellationToken;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::observer::{EventLog, SolverEvent};
+    use crate::parsers::load_csv;
+    use crate::strategies::{
+        AlternatingInferenceChainStrategy, BacktrackingStrategy, ConstraintPropagationStrategy,
+        DeathBlossomStrategy, ForcingChainStrategy, NishioStrategy, PatternOverlayStrategy,
+        XChainStrategy, XYChainStrategy,
+    };
+    use crate::traits::{HasSlots, Solvable, SolveMetrics};
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[test]
+    fn test_outcomes() {
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        assert_eq!(Solver::with_defaults(&mut sudoku).solve(), Outcome::Solved);
+
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let mut solver = Solver::new(&mut sudoku);
+        solver.add_strategy(Box::new(ConstraintPropagationStrategy));
+        let Outcome::Stuck(snapshot) = solver.solve() else {
+            panic!("Constraint propagation alone doesn't solve hard_1.");
+        };
+        let open = snapshot
+            .values
+            .iter()
+            .filter(|value| value.is_none())
+            .count();
+        assert!(open > 0);
+        assert_eq!(
+            snapshot.candidates.iter().filter(|c| !c.is_empty()).count(),
+            open
+        );
+    }
+
+    #[test]
+    fn test_budget_interrupts_solve() {
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let mut solver = Solver::new(&mut sudoku);
+        solver.add_strategy(Box::new(ConstraintPropagationStrategy));
+        solver.set_backtracking_strategy(Box::new(BacktrackingStrategy::default()));
+        solver.set_budget(Budget::unlimited().with_node_limit(3));
+        assert!(matches!(solver.solve(), Outcome::TimedOut(_)));
+
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        let token = CancellationToken::new();
+        token.cancel();
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        solver.set_budget(Budget::unlimited().with_cancellation(token));
+        let Outcome::Cancelled(snapshot) = solver.solve() else {
+            panic!("Solve was cancelled before starting.");
+        };
+        assert_eq!(
+            snapshot,
+            GridSnapshot::of(&Sudoku9x9::new(load_csv("fixtures/hard_2.csv")))
+        );
+
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        solver.set_budget(Budget::unlimited().with_time_limit(Duration::ZERO));
+        assert!(matches!(solver.solve(), Outcome::TimedOut(_)));
+    }
+
+    #[test]
+    fn test_budget_interrupts_long_strategies() {
+        let strategies: Vec<Box<dyn Strategy<Sudoku9x9>>> = vec![
+            Box::new(XChainStrategy::default()),
+            Box::new(XYChainStrategy::default()),
+            Box::new(AlternatingInferenceChainStrategy::default()),
+            Box::new(DeathBlossomStrategy::default()),
+            Box::new(ForcingChainStrategy::default()),
+            Box::new(NishioStrategy::default()),
+            Box::new(PatternOverlayStrategy::default()),
+        ];
+        let token = CancellationToken::new();
+        token.cancel();
+        let budget = Budget::unlimited().with_cancellation(token);
+        for strategy in strategies {
+            let mut sudoku = singles_solved("fixtures/hard_2.csv");
+            let before = GridSnapshot::of(&sudoku);
+            strategy.run_within(&mut sudoku, &budget);
+            assert_eq!(GridSnapshot::of(&sudoku), before, "{}", strategy.name());
+        }
+    }
+
+    #[rstest]
+    #[case::easy("fixtures/easy.csv", "fixtures/easy_solved.csv")]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    fn test_steps(#[case] puzzle: &str, #[case] solution: &str) {
+        let solution = GridSnapshot::of(&Sudoku9x9::new(load_csv(solution)));
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        solver.set_assume_unique(true);
+
+        let first = solver.step().expect("The fixture starts with a single.");
+        assert!(first.technique().ends_with("Single"));
+        assert_eq!(first.placements().len(), 1);
+        for deduction in std::iter::once(first).chain(solver.steps()) {
+            assert!(!deduction.is_empty());
+            for &(id, value) in deduction.placements() {
+                assert_eq!(solution.values[id - 1], Some(value));
+            }
+            for &(id, value) in deduction.eliminations() {
+                assert_ne!(solution.values[id - 1], Some(value));
+            }
+        }
+        assert!(solver.step().is_none());
+        if puzzle.ends_with("easy.csv") {
+            assert!(sudoku.is_solved());
+        }
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            if let Some(value) = slot.get_value() {
+                assert_eq!(solution.values[slot.id() - 1], Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_with_techniques() {
+        let allowed = |spec: &str| StrategySelection::parse(spec);
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/average.csv"));
+        let report = Solver::with_defaults(&mut sudoku)
+            .solve_with_techniques(&allowed("naked_single"))
+            .unwrap();
+        let Outcome::Stuck(snapshot) = report.outcome else {
+            panic!("average needs hidden singles.");
+        };
+        assert!(report.steps.iter().all(|d| d.technique() == "Naked Single"));
+        assert_eq!(report.needed.first(), Some(&"Hidden Single"));
+        assert!(!report.needed.contains(&"Naked Single"));
+        assert_eq!(snapshot, GridSnapshot::of(&sudoku));
+
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/average.csv"));
+        let report = Solver::with_defaults(&mut sudoku)
+            .solve_with_techniques(&allowed("beginner"))
+            .unwrap();
+        assert_eq!(report.outcome, Outcome::Solved);
+        assert!(report.needed.is_empty());
+
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        let report = solver
+            .solve_with_techniques(&allowed(
+                "naked_single,hidden_single_row,hidden_single_column",
+            ))
+            .unwrap();
+        assert!(matches!(report.outcome, Outcome::Stuck(_)));
+        assert!(!report.needed.is_empty());
+        // The solver keeps its own strategies afterwards.
+        assert!(solver.solve().is_solved());
+
+        // Needed techniques don't depend on the strategies of the solver.
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let report = Solver::new(&mut sudoku)
+            .solve_with_techniques(&allowed("beginner"))
+            .unwrap();
+        assert!(matches!(report.outcome, Outcome::Stuck(_)));
+        assert!(!report.needed.is_empty());
+        assert!(!report.needed.contains(&"Naked Single"));
+    }
+
+    #[test]
+    fn test_solve_with_unknown_technique() {
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/average.csv"));
+        let puzzle = GridSnapshot::of(&sudoku);
+        let result = Solver::with_defaults(&mut sudoku)
+            .solve_with_techniques(&StrategySelection::parse("naked_single,Hidden Single"));
+        assert_eq!(
+            result.err(),
+            Some(UnknownStrategy("Hidden Single".to_string()))
+        );
+        assert_eq!(GridSnapshot::of(&sudoku), puzzle);
+    }
+
+    #[test]
+    fn test_observers() {
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let open = sudoku.slots().len() - sudoku.count_solved_slots();
+        let log = Rc::new(RefCell::new(EventLog::default()));
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        solver.add_observer(log.clone());
+        assert!(solver.solve().is_solved());
+
+        let events = &log.borrow().events;
+        assert!(matches!(events[0], SolverEvent::SolverStarted(_)));
+        assert_eq!(
+            events.last(),
+            Some(&SolverEvent::SolverFinished(Outcome::Solved))
+        );
+        let count =
+            |matches: fn(&SolverEvent) -> bool| events.iter().filter(|e| matches(e)).count();
+        assert_eq!(count(|e| matches!(e, SolverEvent::Placed { .. })), open);
+        assert!(count(|e| matches!(e, SolverEvent::Eliminated { .. })) > 0);
+        assert_eq!(
+            count(|e| matches!(e, SolverEvent::StrategyStarted(_))),
+            count(|e| matches!(e, SolverEvent::StrategyFinished { .. }))
+        );
+        assert_eq!(
+            events[1],
+            SolverEvent::StrategyStarted("ConstraintPropagationStrategy".to_string())
+        );
+
+        // r1c1 is 2 in the only solution, so every guess is taken back.
+        let mut grid = load_csv("fixtures/hard_1.csv");
+        grid[0][0] = Some(5);
+        let mut sudoku = Sudoku9x9::new(grid);
+        let log = Rc::new(RefCell::new(EventLog::default()));
+        let mut solver = Solver::new(&mut sudoku);
+        solver.set_backtracking_strategy(Box::new(BacktrackingStrategy::default()));
+        solver.add_observer(log.clone());
+        assert!(matches!(solver.solve(), Outcome::Stuck(_)));
+        let events = &log.borrow().events;
+        let count =
+            |matches: fn(&SolverEvent) -> bool| events.iter().filter(|e| matches(e)).count();
+        let guesses = count(|e| matches!(e, SolverEvent::Guessed { .. }));
+        assert!(guesses > 0);
+        assert_eq!(count(|e| matches!(e, SolverEvent::Undone { .. })), guesses);
+        assert!(events.contains(&SolverEvent::StrategyFinished {
+            strategy: "BacktrackingStrategy".to_string(),
+            progress: false,
+        }));
+
+        // Steps report their placements only.
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/easy.csv"));
+        let log = Rc::new(RefCell::new(EventLog::default()));
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        solver.add_observer(log.clone());
+        let deduction = solver.step().expect("easy has a naked single.");
+        let (slot_id, value) = deduction.placements()[0];
+        assert_eq!(
+            log.borrow().events,
+            [SolverEvent::Placed { slot_id, value }]
+        );
+    }
+}
@@ -0,0 +1,586 @@
+use crate::helpers::{digits, peer_ids, position, run_deductions, run_found_deductions, subsets};
+use crate::objects::budget::Budget;
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// Largest ALS looked for, in cells.
+const MAX_ALS_CELLS: usize = 5;
+
+/// Almost Locked Set: `n` cells of one unit holding `n + 1` candidates together.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Als {
+    pub(crate) cells: Vec<usize>,
+    pub(crate) mask: u32,
+}
+
+impl Als {
+    pub(crate) fn has(&self, value: u8) -> bool {
+        self.mask & (1 << value) != 0
+    }
+
+    fn is_disjoint(&self, other: &Als) -> bool {
+        self.cells.iter().all(|id| !other.cells.contains(id))
+    }
+}
+
+/// Candidates (as bit masks) and peers of unsolved cells, with every ALS of the grid
+/// of at most `MAX_ALS_CELLS` cells.
+pub(crate) struct AlsGrid {
+    pub(crate) masks: HashMap<usize, u32>,
+    peers: HashMap<usize, HashSet<usize>>,
+    names: HashMap<usize, String>,
+    pub(crate) sets: Vec<Als>,
+}
+
+impl AlsGrid {
+    pub(crate) fn new<S: SimpleSudoku>(sudoku: &S) -> Self {
+        let mut masks = HashMap::new();
+        let mut peers = HashMap::new();
+        let mut names = HashMap::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            if slot.is_solved() {
+                continue;
+            }
+            let mask = slot
+                .candidates()
+                .iter()
+                .fold(0u32, |mask, value| mask | (1 << value));
+            let (row, column, _) = position(&slot);
+            masks.insert(slot.id(), mask);
+            peers.insert(slot.id(), peer_ids(&slot));
+            names.insert(slot.id(), format!("r{row}c{column}"));
+        }
+
+        let mut sets: Vec<Als> = Vec::new();
+        let mut known: HashSet<Als> = HashSet::new();
+        for unit in sudoku.units() {
+            let open: Vec<usize> = unit
+                .iter()
+                .map(|slot| slot.borrow().id())
+                .filter(|id| masks.contains_key(id))
+                .collect();
+            // Subsets of the open cells up to the size limit, except the whole unit.
+            for mut cells in subsets(&open, MAX_ALS_CELLS) {
+                if cells.len() == open.len() {
+                    continue;
+                }
+                let mask = cells.iter().fold(0u32, |mask, id| mask | masks[id]);
+                if mask.count_ones() as usize != cells.len() + 1 {
+                    continue;
+                }
+                cells.sort_unstable();
+                let als = Als { cells, mask };
+                if known.insert(als.clone()) {
+                    sets.push(als);
+                }
+            }
+        }
+        AlsGrid {
+            masks,
+            peers,
+            names,
+            sets,
+        }
+    }
+
+    pub(crate) fn cells_with(&self, cells: &[usize], value: u8) -> Vec<usize> {
+        cells
+            .iter()
+            .copied()
+            .filter(|id| self.masks[id] & (1 << value) != 0)
+            .collect()
+    }
+
+    pub(crate) fn sees(&self, first: usize, second: usize) -> bool {
+        self.peers[&first].contains(&second)
+    }
+
+    /// Restricted common candidates: digits of both sets whose cells all see each other.
+    pub(crate) fn rccs(&self, first: &Als, second: &Als) -> Vec<u8> {
+        if !first.is_disjoint(second) {
+            return Vec::new();
+        }
+        digits(first.mask & second.mask)
+            .filter(|&value| {
+                let theirs = self.cells_with(&second.cells, value);
+                self.cells_with(&first.cells, value)
+                    .iter()
+                    .all(|&id| theirs.iter().all(|&other| self.sees(id, other)))
+            })
+            .collect()
+    }
+
+    /// Cells outside `excluded` holding `value` and seeing all of `seen_by`.
+    pub(crate) fn targets(&self, value: u8, seen_by: &[usize], excluded: &[usize]) -> Vec<usize> {
+        if seen_by.is_empty() {
+            return Vec::new();
+        }
+        let mut targets: Vec<usize> = self
+            .masks
+            .iter()
+            .filter(|(id, mask)| {
+                *mask & (1 << value) != 0
+                    && !excluded.contains(id)
+                    && seen_by.iter().all(|other| self.sees(**id, *other))
+            })
+            .map(|(&id, _)| id)
+            .collect();
+        targets.sort_unstable();
+        targets
+    }
+
+    pub(crate) fn describe(&self, als: &Als) -> String {
+        let cells: Vec<&str> = als.cells.iter().map(|id| self.names[id].as_str()).collect();
+        let values: Vec<String> = digits(als.mask).map(|value| value.to_string()).collect();
+        format!("{} {{{}}}", cells.join(" "), values.join(""))
+    }
+
+    pub(crate) fn name(&self, id: usize) -> &str {
+        &self.names[&id]
+    }
+}
+
+/// Remove `value` from cells seeing every `value` cell of the sets.
+fn eliminate_common(grid: &AlsGrid, deduction: &mut Deduction, sets: &[&Als], value: u8) {
+    let mut seen_by = Vec::new();
+    let mut excluded = Vec::new();
+    for als in sets {
+        seen_by.extend(grid.cells_with(&als.cells, value));
+        excluded.extend(als.cells.iter().copied());
+    }
+    for target in grid.targets(value, &seen_by, &excluded) {
+        deduction.eliminate(target, value);
+    }
+}
+
+fn pattern_cells(sets: &[&Als]) -> Vec<usize> {
+    sets.iter()
+        .flat_map(|als| als.cells.iter().copied())
+        .collect()
+}
+
+/// Two ALSs sharing a restricted common candidate `x`: any other common digit `z`
+/// is removed from cells seeing all its cells in both sets. With two RCCs (doubly
+/// linked) every digit of each set is locked in it.
+#[derive(Debug)]
+pub struct AlsXzStrategy;
+
+impl Display for AlsXzStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AlsXzStrategy")
+    }
+}
+
+impl AlsXzStrategy {
+    fn find(&self, grid: &AlsGrid) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+
+        for (idx, first) in grid.sets.iter().enumerate() {
+            for second in &grid.sets[idx + 1..] {
+                let rccs = grid.rccs(first, second);
+                if rccs.is_empty() || rccs.len() > 2 {
+                    continue;
+                }
+                let sets = [first, second];
+                let mut deduction = Deduction::new("ALS-XZ", pattern_cells(&sets));
+                for z in digits(first.mask & second.mask).filter(|z| !rccs.contains(z)) {
+                    eliminate_common(grid, &mut deduction, &sets, z);
+                }
+                if rccs.len() == 2 {
+                    // Doubly linked: each set becomes a locked set without the RCCs.
+                    for als in sets {
+                        for value in digits(als.mask).filter(|value| !rccs.contains(value)) {
+                            eliminate_common(grid, &mut deduction, &[als], value);
+                        }
+                    }
+                    for &x in &rccs {
+                        eliminate_common(grid, &mut deduction, &sets, x);
+                    }
+                }
+                if deduction.is_empty() {
+                    continue;
+                }
+                let rccs_text: Vec<String> = rccs.iter().map(|x| x.to_string()).collect();
+                deduction.set_explanation(format!(
+                    "A={} B={} RCC={}",
+                    grid.describe(first),
+                    grid.describe(second),
+                    rccs_text.join(",")
+                ));
+                deductions.push(deduction);
+            }
+        }
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for AlsXzStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(&AlsGrid::new(sudoku))
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["ALS-XZ"]
+    }
+}
+
+/// Pivot ALS `C` linked to `A` by RCC `x` and to `B` by RCC `y`: a digit `z`
+/// common to `A` and `B` is removed from cells seeing all its cells in both.
+#[derive(Debug)]
+pub struct AlsXyWingStrategy;
+
+impl Display for AlsXyWingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AlsXyWingStrategy")
+    }
+}
+
+impl AlsXyWingStrategy {
+    fn find(&self, grid: &AlsGrid) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+
+        // RCCs of every linked pair, both directions.
+        let mut links: Vec<Vec<(usize, Vec<u8>)>> = vec![Vec::new(); grid.sets.len()];
+        for first in 0..grid.sets.len() {
+            for second in first + 1..grid.sets.len() {
+                let rccs = grid.rccs(&grid.sets[first], &grid.sets[second]);
+                if !rccs.is_empty() {
+                    links[first].push((second, rccs.clone()));
+                    links[second].push((first, rccs));
+                }
+            }
+        }
+
+        for (pivot, linked) in links.iter().enumerate() {
+            for (idx, (a, a_rccs)) in linked.iter().enumerate() {
+                for (b, b_rccs) in &linked[idx + 1..] {
+                    let (first, second) = (&grid.sets[*a], &grid.sets[*b]);
+                    if !first.is_disjoint(second) {
+                        continue;
+                    }
+                    let Some((x, y)) = a_rccs
+                        .iter()
+                        .flat_map(|&x| b_rccs.iter().map(move |&y| (x, y)))
+                        .find(|(x, y)| x != y)
+                    else {
+                        continue;
+                    };
+                    let sets = [first, second, &grid.sets[pivot]];
+                    let mut deduction = Deduction::new("ALS-XY-Wing", pattern_cells(&sets));
+                    for z in digits(first.mask & second.mask).filter(|&z| z != x && z != y) {
+                        eliminate_common(grid, &mut deduction, &[first, second], z);
+                    }
+                    if deduction.is_empty() {
+                        continue;
+                    }
+                    deduction.set_explanation(format!(
+                        "A={} B={} C={} x={x} y={y}",
+                        grid.describe(first),
+                        grid.describe(second),
+                        grid.describe(&grid.sets[pivot])
+                    ));
+                    deductions.push(deduction);
+                }
+            }
+        }
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for AlsXyWingStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(&AlsGrid::new(sudoku))
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["ALS-XY-Wing"]
+    }
+}
+
+/// Stem cell whose every candidate `d` has a petal ALS with all its `d` cells seeing
+/// the stem: a digit `z` common to all petals is removed from cells seeing all of
+/// the petals' `z` cells.
+#[derive(Debug)]
+pub struct DeathBlossomStrategy {
+    /// Largest number of stem candidates to try.
+    pub max_stem_size: usize,
+}
+
+impl Default for DeathBlossomStrategy {
+    fn default() -> Self {
+        Self { max_stem_size: 3 }
+    }
+}
+
+impl Display for DeathBlossomStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DeathBlossomStrategy")
+    }
+}
+
+impl DeathBlossomStrategy {
+    /// Pick pairwise disjoint petals, one per stem candidate, sharing a digit `z`.
+    fn blossoms(petals: &[Vec<&Als>], chosen: &mut Vec<usize>, found: &mut Vec<Vec<usize>>) {
+        let depth = chosen.len();
+        if depth == petals.len() {
+            found.push(chosen.clone());
+            return;
+        }
+        for (idx, petal) in petals[depth].iter().enumerate() {
+            let fits = chosen.iter().enumerate().all(|(level, &other)| {
+                let other = petals[level][other];
+                petal.is_disjoint(other) && petal.mask & other.mask != 0
+            });
+            if fits {
+                chosen.push(idx);
+                Self::blossoms(petals, chosen, found);
+                chosen.pop();
+            }
+        }
+    }
+
+    /// Deductions found before the budget runs out, checked before every stem.
+    fn find(&self, grid: &AlsGrid, budget: &Budget) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        let mut stems: Vec<usize> = grid.masks.keys().copied().collect();
+        stems.sort_unstable();
+
+        for stem in stems {
+            if budget.check().is_some() {
+                break;
+            }
+            let stem_mask = grid.masks[&stem];
+            let size = stem_mask.count_ones() as usize;
+            if size < 2 || size > self.max_stem_size {
+                continue;
+            }
+            let petals: Vec<Vec<&Als>> = digits(stem_mask)
+                .map(|value| {
+                    grid.sets
+                        .iter()
+                        .filter(|als| {
+                            als.has(value)
+                                && !als.cells.contains(&stem)
+                                && grid
+                                    .cells_with(&als.cells, value)
+                                    .iter()
+                                    .all(|&id| grid.sees(id, stem))
+                        })
+                        .collect()
+                })
+                .collect();
+            if petals.iter().any(|options| options.is_empty()) {
+                continue;
+            }
+
+            let mut found = Vec::new();
+            Self::blossoms(&petals, &mut Vec::new(), &mut found);
+            for choice in found {
+                let sets: Vec<&Als> = choice
+                    .iter()
+                    .enumerate()
+                    .map(|(level, &idx)| petals[level][idx])
+                    .collect();
+                let common = sets.iter().fold(u32::MAX, |mask, als| mask & als.mask) & !stem_mask;
+                let mut cells = vec![stem];
+                cells.extend(pattern_cells(&sets));
+                let mut deduction = Deduction::new("Death Blossom", cells.clone());
+                for z in digits(common) {
+                    let mut seen_by = Vec::new();
+                    for als in &sets {
+                        seen_by.extend(grid.cells_with(&als.cells, z));
+                    }
+                    for target in grid.targets(z, &seen_by, &cells) {
+                        deduction.eliminate(target, z);
+                    }
+                }
+                if deduction.is_empty() {
+                    continue;
+                }
+                let petals_text: Vec<String> = sets.iter().map(|als| grid.describe(als)).collect();
+                deduction.set_explanation(format!(
+                    "stem {} petals {}",
+                    grid.name(stem),
+                    petals_text.join(", ")
+                ));
+                deductions.push(deduction);
+            }
+        }
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for DeathBlossomStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(&AlsGrid::new(sudoku), &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| {
+            self.find(&AlsGrid::new(sudoku), budget)
+        });
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Death Blossom"]
+    }
+}
+
+/// ALS-XZ, ALS-XY-Wing and Death Blossom in that order, all from one `AlsGrid` so the
+/// sets of the grid are only looked for once per step.
+#[derive(Debug, Default)]
+pub struct AlmostLockedSetStrategy {
+    pub death_blossom: DeathBlossomStrategy,
+}
+
+impl Display for AlmostLockedSetStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AlmostLockedSetStrategy")
+    }
+}
+
+impl AlmostLockedSetStrategy {
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        let grid = AlsGrid::new(sudoku);
+        let mut deductions = AlsXzStrategy.find(&grid);
+        deductions.extend(AlsXyWingStrategy.find(&grid));
+        deductions.extend(self.death_blossom.find(&grid, budget));
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for AlmostLockedSetStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["ALS-XZ", "ALS-XY-Wing", "Death Blossom"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::with_candidates;
+
+    #[test]
+    fn test_als_xz() {
+        // A = r1c1 {12}, B = r1c5 r2c5 {123}, RCC 1 in row 1, so 2 is in r1c1 or r2c5.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (5, &[1, 3]), (14, &[2, 3])]);
+        let deductions = AlsXzStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [1, 5, 14])
+            .expect("ALS-XZ is not found.");
+        assert_eq!(
+            found.explanation(),
+            Some("A=r1c1 {12} B=r1c5 r2c5 {123} RCC=1")
+        );
+        assert_eq!(
+            found.eliminations(),
+            &[(4, 2), (6, 2), (10, 2), (11, 2), (12, 2)]
+        );
+    }
+
+    #[test]
+    fn test_doubly_linked_als_xz() {
+        // A = r1c1 r2c2 {124}, B = r1c5 r2c5 {123}, RCCs 1 in row 1 and 2 in row 2:
+        // A locks 4 in area 1, B locks 3 in area 2 and column 5, 1 and 2 lock their rows.
+        let sudoku = with_candidates(&[(1, &[1, 4]), (11, &[2, 4]), (5, &[1, 3]), (14, &[2, 3])]);
+        let deductions = AlsXzStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [5, 14, 1, 11])
+            .expect("Doubly linked ALS-XZ is not found.");
+        assert_eq!(
+            found.explanation(),
+            Some("A=r1c5 r2c5 {123} B=r1c1 r2c2 {124} RCC=1,2")
+        );
+        let mut eliminations = found.eliminations().to_vec();
+        eliminations.sort_unstable();
+        let mut expected: Vec<(usize, u8)> = [2, 3, 10, 12, 19, 20, 21]
+            .map(|id| (id, 4))
+            .into_iter()
+            .chain([4, 6, 13, 15, 22, 23, 24, 32, 41, 50, 59, 68, 77].map(|id| (id, 3)))
+            .chain([2, 3, 4, 6, 7, 8, 9].map(|id| (id, 1)))
+            .chain([10, 12, 13, 15, 16, 17, 18].map(|id| (id, 2)))
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(eliminations, expected);
+    }
+
+    #[test]
+    fn test_als_xy_wing() {
+        // C = r1c1 {12} links A = r1c5 {13} by 1 and B = r5c1 {23} by 2, so 3 is in
+        // r1c5 or r5c1.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (5, &[1, 3]), (37, &[2, 3])]);
+        let deductions = AlsXyWingStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [5, 37, 1])
+            .expect("ALS-XY-Wing is not found.");
+        assert_eq!(
+            found.explanation(),
+            Some("A=r1c5 {13} B=r5c1 {23} C=r1c1 {12} x=1 y=2")
+        );
+        assert_eq!(found.eliminations(), &[(41, 3)]);
+    }
+
+    #[test]
+    fn test_death_blossom() {
+        // Stem r1c1 {124} with petals r1c5 {13}, r2c1 {23} and r2c2 {34}: whichever
+        // value the stem takes, one petal holds 3.
+        let sudoku =
+            with_candidates(&[(1, &[1, 2, 4]), (5, &[1, 3]), (10, &[2, 3]), (11, &[3, 4])]);
+        let deductions = DeathBlossomStrategy::default().deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [1, 5, 10, 11])
+            .expect("Death Blossom is not found.");
+        assert_eq!(
+            found.explanation(),
+            Some("stem r1c1 petals r1c5 {13}, r2c1 {23}, r2c2 {34}")
+        );
+        let mut eliminations = found.eliminations().to_vec();
+        eliminations.sort_unstable();
+        assert_eq!(eliminations, [(2, 3), (3, 3), (13, 3), (14, 3), (15, 3)]);
+    }
+
+    #[test]
+    fn test_almost_locked_sets() {
+        // The Death Blossom grid also holds ALS-XZ patterns, all found from one grid.
+        let sudoku =
+            with_candidates(&[(1, &[1, 2, 4]), (5, &[1, 3]), (10, &[2, 3]), (11, &[3, 4])]);
+        let deductions = AlmostLockedSetStrategy::default().deductions(&sudoku);
+        let mut expected = AlsXzStrategy.deductions(&sudoku);
+        expected.extend(AlsXyWingStrategy.deductions(&sudoku));
+        expected.extend(DeathBlossomStrategy::default().deductions(&sudoku));
+        assert_eq!(deductions, expected);
+        assert!(deductions.iter().any(|d| d.technique() == "ALS-XZ"));
+        assert!(deductions.iter().any(|d| d.technique() == "Death Blossom"));
+    }
+}
@@ -1,14 +1,117 @@
+use crate::objects::budget::Budget;
+use crate::observer::SolverObserver;
 use crate::traits::{Identifiable, SimpleSudoku, Strategy, SudokuConfig};
-use std::collections::HashSet;
-use tracing::{debug, error, info, trace};
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tracing::{debug, error, info, trace, warn};
 
-pub struct BacktrackingStrategy;
+/// What is deduced at every search node after a guess, each level adds to the previous.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Propagation {
+    /// Only remove the guessed value from the peers.
+    #[default]
+    Peers,
+    /// Place slots left with a single candidate.
+    NakedSingles,
+    /// Place values left with a single slot in a unit, fail on values with none.
+    Singles,
+    /// Remove naked pairs from the rest of their units.
+    Subsets,
+}
+
+/// Which branch the search opens next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VariableOrder {
+    /// Slot with the fewest candidates.
+    #[default]
+    Mrv,
+    /// Slot with the fewest candidates, ties broken by the most unsolved peers.
+    MrvDegree,
+    /// Slot with the fewest candidates, or the places of a digit in a unit when a
+    /// digit has fewer places left.
+    UnitDigit,
+}
+
+/// Order in which the alternatives of a branch are tried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValueOrder {
+    /// Smallest value first, lowest slot first when branching on a digit.
+    #[default]
+    Ascending,
+    /// Alternatives removing the fewest candidates from the peers first.
+    LeastConstraining,
+    /// Shuffled by a generator seeded with the value: the same seed gives the same search.
+    Random(u64),
+}
+
+/// Search effort of the last run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Guesses made.
+    pub nodes: usize,
+    /// Guesses that failed and were undone.
+    pub backtracks: usize,
+}
 
+#[derive(Debug, Default)]
+pub struct BacktrackingStrategy {
+    pub propagation: Propagation,
+    pub variable_order: VariableOrder,
+    pub value_order: ValueOrder,
+    stats: Cell<SearchStats>,
+}
+
+impl BacktrackingStrategy {
+    pub fn with_propagation(propagation: Propagation) -> Self {
+        Self {
+            propagation,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_variable_order(mut self, variable_order: VariableOrder) -> Self {
+        self.variable_order = variable_order;
+        self
+    }
+
+    pub fn with_value_order(mut self, value_order: ValueOrder) -> Self {
+        self.value_order = value_order;
+        self
+    }
+
+    /// Node and backtrack counters of the last run.
+    pub fn stats(&self) -> SearchStats {
+        self.stats.get()
+    }
+}
+
+/// Saved candidates of a placed slot and its peers.
+struct Undo {
+    idx: usize,
+    current: HashSet<u8>,
+    peers: Vec<(usize, HashSet<u8>)>,
+}
+
+#[derive(Clone)]
 struct BacktrackState {
     values: Vec<Option<u8>>,
     candidates: Vec<HashSet<u8>>,
     peers: Vec<Vec<usize>>, // precomputed once
+    units: Vec<Vec<usize>>,
     total_slots: usize,
+    length: u8,
+    propagation: Propagation,
+    variable_order: VariableOrder,
+    value_order: ValueOrder,
+    rng: u64,
+    stats: SearchStats,
+    stop: Option<Arc<AtomicBool>>,
+    budget: Option<Budget>,
 }
 
 impl BacktrackState {
@@ -23,18 +126,49 @@ impl BacktrackState {
             let slot = slot.borrow();
             let idx = slot.id() - 1;
             values[idx] = slot.get_value();
-            candidates[idx] = slot.variants();
+            candidates[idx] = slot.candidates();
         }
 
         let peers = Self::compute_peers::<S>();
+        let units = Self::compute_units::<S>();
         Self {
             values,
             candidates,
             peers,
+            units,
             total_slots,
+            length: S::LENGTH as u8,
+            propagation: Propagation::default(),
+            variable_order: VariableOrder::default(),
+            value_order: ValueOrder::default(),
+            rng: 0,
+            stats: SearchStats::default(),
+            stop: None,
+            budget: None,
         }
     }
 
+    fn compute_units<S: SudokuConfig>() -> Vec<Vec<usize>> {
+        let num_rows = S::NUMBER_OF_ROWS;
+        let num_cols = S::NUMBER_OF_COLS;
+        let area_rows = S::NUMBER_OF_ROWS_IN_AREA;
+        let area_cols = S::NUMBER_OF_COLS_IN_AREA;
+
+        let rows = (0..num_rows).map(|row| (0..num_cols).map(|col| row * num_cols + col).collect());
+        let columns =
+            (0..num_cols).map(|col| (0..num_rows).map(|row| row * num_cols + col).collect());
+        let areas = (0..num_rows).step_by(area_rows).flat_map(|area_row| {
+            (0..num_cols).step_by(area_cols).map(move |area_col| {
+                (area_row..area_row + area_rows)
+                    .flat_map(|row| {
+                        (area_col..area_col + area_cols).map(move |col| row * num_cols + col)
+                    })
+                    .collect()
+            })
+        });
+        rows.chain(columns).chain(areas).collect()
+    }
+
     fn compute_peers<S: SudokuConfig>() -> Vec<Vec<usize>> {
         let total_slots = S::total_number_of_slots();
         let num_rows = S::NUMBER_OF_ROWS;
@@ -75,6 +209,222 @@ impl BacktrackState {
             .collect()
     }
 
+    /// Place the value and remove it from the peers. Returns what is needed to undo
+    /// it, and the first peer left without candidates, if any.
+    fn place(&mut self, idx: usize, value: u8) -> (Undo, Option<usize>) {
+        let undo = Undo {
+            idx,
+            current: self.candidates[idx].clone(),
+            peers: self.peers[idx]
+                .iter()
+                .map(|&p| (p, self.candidates[p].clone()))
+                .collect(),
+        };
+        (undo, self.assign(idx, value))
+    }
+
+    /// Set the value and remove it from the peers, returns the first peer left
+    /// without candidates, if any.
+    fn assign(&mut self, idx: usize, value: u8) -> Option<usize> {
+        self.values[idx] = Some(value);
+        self.candidates[idx] = HashSet::from([value]);
+
+        for &peer in &self.peers[idx] {
+            if self.values[peer].is_none() {
+                self.candidates[peer].remove(&value);
+                // Check if peer still has at least one candidate
+                if self.candidates[peer].is_empty() {
+                    return Some(peer);
+                }
+            }
+        }
+        None
+    }
+
+    /// Deduce what the propagation level allows, `false` on a contradiction.
+    fn propagate(&mut self) -> bool {
+        if self.propagation == Propagation::Peers {
+            return true;
+        }
+        loop {
+            let mut changed = false;
+            for idx in 0..self.total_slots {
+                if self.values[idx].is_some() || self.candidates[idx].len() != 1 {
+                    continue;
+                }
+                let value = *self.candidates[idx]
+                    .iter()
+                    .next()
+                    .expect("Single candidate.");
+                if self.assign(idx, value).is_some() {
+                    return false;
+                }
+                changed = true;
+            }
+
+            if self.propagation >= Propagation::Singles {
+                for unit in 0..self.units.len() {
+                    for value in 1..=self.length {
+                        let mut places = self.units[unit]
+                            .iter()
+                            .copied()
+                            .filter(|&idx| self.candidates[idx].contains(&value));
+                        match (places.next(), places.next()) {
+                            (None, _) => return false,
+                            (Some(idx), None) if self.values[idx].is_none() => {
+                                if self.assign(idx, value).is_some() {
+                                    return false;
+                                }
+                                changed = true;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            if self.propagation >= Propagation::Subsets {
+                for unit in self.units.clone() {
+                    let pairs: Vec<usize> = unit
+                        .iter()
+                        .copied()
+                        .filter(|&idx| {
+                            self.values[idx].is_none() && self.candidates[idx].len() == 2
+                        })
+                        .collect();
+                    for (pos, &first) in pairs.iter().enumerate() {
+                        for &second in &pairs[pos + 1..] {
+                            if self.candidates[first] != self.candidates[second] {
+                                continue;
+                            }
+                            let pair = self.candidates[first].clone();
+                            for &other in &unit {
+                                if other == first || other == second || self.values[other].is_some()
+                                {
+                                    continue;
+                                }
+                                for value in &pair {
+                                    changed |= self.candidates[other].remove(value);
+                                }
+                                if self.candidates[other].is_empty() {
+                                    return false;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if !changed {
+                return true;
+            }
+        }
+    }
+
+    /// Whether the search has to stop, by the stop flag or an exhausted budget.
+    fn interrupted(&self) -> bool {
+        self.stop
+            .as_ref()
+            .is_some_and(|stop| stop.load(Ordering::Relaxed))
+            || self
+                .budget
+                .as_ref()
+                .is_some_and(|budget| budget.check().is_some())
+    }
+
+    /// Restore the slot and all its peers.
+    fn undo(&mut self, undo: Undo) {
+        self.values[undo.idx] = None;
+        self.candidates[undo.idx] = undo.current;
+        for (peer, saved_candidates) in undo.peers {
+            self.candidates[peer] = saved_candidates;
+        }
+    }
+
+    /// Alternatives (slot, value) of the next branch, one of which holds in every
+    /// solution. `None` when solved, empty on a contradiction.
+    fn branch(&mut self) -> Option<Vec<(usize, u8)>> {
+        let open = (0..self.total_slots).filter(|&i| self.values[i].is_none());
+        let idx = match self.variable_order {
+            VariableOrder::MrvDegree => open.min_by_key(|&i| {
+                let degree = self.peers[i]
+                    .iter()
+                    .filter(|&&peer| self.values[peer].is_none())
+                    .count();
+                (self.candidates[i].len(), Reverse(degree))
+            })?,
+            _ => open.min_by_key(|&i| self.candidates[i].len())?,
+        };
+        let mut alternatives: Vec<(usize, u8)> = self.candidates[idx]
+            .iter()
+            .map(|&value| (idx, value))
+            .collect();
+        if self.variable_order == VariableOrder::UnitDigit
+            && let Some(places) = self.fewest_places()
+            && places.len() < alternatives.len()
+        {
+            alternatives = places;
+        }
+        self.order(&mut alternatives);
+        Some(alternatives)
+    }
+
+    /// Places of the missing digit with the fewest of them in a unit.
+    fn fewest_places(&self) -> Option<Vec<(usize, u8)>> {
+        let mut best: Option<Vec<(usize, u8)>> = None;
+        for unit in &self.units {
+            for value in 1..=self.length {
+                if unit.iter().any(|&i| self.values[i] == Some(value)) {
+                    continue;
+                }
+                let places: Vec<(usize, u8)> = unit
+                    .iter()
+                    .copied()
+                    .filter(|&i| self.values[i].is_none() && self.candidates[i].contains(&value))
+                    .map(|i| (i, value))
+                    .collect();
+                if best.as_ref().is_none_or(|best| places.len() < best.len()) {
+                    best = Some(places);
+                }
+            }
+        }
+        best
+    }
+
+    fn order(&mut self, alternatives: &mut [(usize, u8)]) {
+        alternatives.sort_unstable();
+        match self.value_order {
+            ValueOrder::Ascending => {}
+            ValueOrder::LeastConstraining => {
+                // Stable, so ties stay ascending.
+                alternatives.sort_by_key(|&(idx, value)| {
+                    self.peers[idx]
+                        .iter()
+                        .filter(|&&peer| {
+                            self.values[peer].is_none() && self.candidates[peer].contains(&value)
+                        })
+                        .count()
+                });
+            }
+            ValueOrder::Random(_) => {
+                // Fisher-Yates shuffle.
+                for i in (1..alternatives.len()).rev() {
+                    let j = (self.next_random() % (i as u64 + 1)) as usize;
+                    alternatives.swap(i, j);
+                }
+            }
+        }
+    }
+
+    /// SplitMix64 step, good enough to shuffle a handful of alternatives.
+    fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
     fn apply_solution<S: SimpleSudoku>(&self, sudoku: &mut S) {
         // Write a solution back into your Rc<RefCell<>> graph
         for slot in sudoku.slots() {
@@ -87,68 +437,73 @@ impl BacktrackState {
     }
 }
 
-fn solve(state: &mut BacktrackState, depth: usize) -> bool {
-    // Find the unsolved slot with the fewest candidates (MRV heuristic).
-    let Some(idx) = (0..state.total_slots)
-        .filter(|&i| state.values[i].is_none())
-        .min_by_key(|&i| state.candidates[i].len())
-    else {
-        debug!("BacktrackingStrategy: Solution found at depth {}.", depth);
+/// Depth-first search calling `visit` with the values of every solution until it
+/// asks to stop by returning `true`. Returns `true` once stopped, by `visit` or by the
+/// stop flag or the budget, leaving the grid as it was at that point.
+fn solve(
+    state: &mut BacktrackState,
+    depth: usize,
+    visit: &mut dyn FnMut(&[Option<u8>]) -> bool,
+    observer: &mut dyn SolverObserver,
+) -> bool {
+    if state.interrupted() {
+        debug!("BacktrackingStrategy: Interrupted at depth {}.", depth);
         return true;
+    }
+
+    // Pick the next branch according to the variable ordering.
+    let Some(alternatives) = state.branch() else {
+        debug!("BacktrackingStrategy: Solution found at depth {}.", depth);
+        return visit(&state.values);
     };
 
-    // If a slot has no candidates, this branch is invalid
-    if state.candidates[idx].is_empty() {
+    // If a slot or a digit has nowhere to go, this branch is invalid
+    if alternatives.is_empty() {
         debug!(
-            "BacktrackingStrategy: No candidates for slot {} at depth {}.",
-            idx + 1,
+            "BacktrackingStrategy: Nothing to branch on at depth {}.",
             depth
         );
         return false;
     }
 
-    let candidates: Vec<u8> = state.candidates[idx].iter().copied().collect();
     trace!(
-        "BacktrackingStrategy: Trying slot {} (depth {}) with {} candidates: {:?}.",
-        idx + 1,
+        "BacktrackingStrategy: Branching (depth {}) on {} alternatives: {:?}.",
         depth,
-        candidates.len(),
-        candidates
+        alternatives.len(),
+        alternatives
     );
 
-    for candidate in candidates {
-        // Save the current slot's candidates and all affected peers
-        let saved_current = state.candidates[idx].clone();
-        let saved_peers: Vec<(usize, HashSet<u8>)> = state.peers[idx]
-            .iter()
-            .map(|&p| (p, state.candidates[p].clone()))
-            .collect();
-
-        // Apply the candidate
-        state.values[idx] = Some(candidate);
-        state.candidates[idx] = HashSet::from([candidate]);
+    for (idx, candidate) in alternatives {
+        state.stats.nodes += 1;
+        if let Some(budget) = &state.budget {
+            budget.spend(1);
+        }
+        // Propagation beyond peers touches any slot, so the whole grid is saved.
+        let snapshot = (state.propagation > Propagation::Peers)
+            .then(|| (state.values.clone(), state.candidates.clone()));
 
-        // Propagate constraint: remove this candidate from all peers
-        let mut valid = true;
-        for &peer in &state.peers[idx] {
-            if state.values[peer].is_none() {
-                state.candidates[peer].remove(&candidate);
-                // Check if peer still has at least one candidate
-                if state.candidates[peer].is_empty() {
-                    valid = false;
-                    debug!(
-                        "BacktrackingStrategy: Candidate {} for slot {} conflicts with peer {}.",
-                        candidate,
-                        idx + 1,
-                        peer + 1
-                    );
-                    break;
-                }
-            }
+        // Apply the candidate and propagate it to the peers.
+        observer.guessed(idx + 1, candidate, depth);
+        let (undo, emptied) = state.place(idx, candidate);
+        let mut valid = emptied.is_none();
+        if let Some(peer) = emptied {
+            debug!(
+                "BacktrackingStrategy: Candidate {} for slot {} conflicts with peer {}.",
+                candidate,
+                idx + 1,
+                peer + 1
+            );
+        } else if !state.propagate() {
+            valid = false;
+            debug!(
+                "BacktrackingStrategy: Candidate {} for slot {} leads to a contradiction.",
+                candidate,
+                idx + 1
+            );
         }
 
         // Recursively solve if constraints are satisfied
-        if valid && solve(state, depth + 1) {
+        if valid && solve(state, depth + 1, visit, observer) {
             return true;
         }
 
@@ -159,18 +514,154 @@ fn solve(state: &mut BacktrackState, depth: usize) -> bool {
         );
 
         // Undo: restore the current slot and all peers.
-        state.values[idx] = None;
-        state.candidates[idx] = saved_current;
-        for (peer, saved_candidates) in saved_peers {
-            state.candidates[peer] = saved_candidates;
+        state.stats.backtracks += 1;
+        observer.undone(idx + 1, candidate, depth);
+        match snapshot {
+            Some((values, candidates)) => {
+                state.values = values;
+                state.candidates = candidates;
+            }
+            None => state.undo(undo),
         }
     }
 
     false
 }
 
+/// Branch being explored: its alternatives, the next one to try and the undo of the
+/// one placed last.
+struct Frame {
+    alternatives: Vec<(usize, u8)>,
+    next: usize,
+    undo: Option<Undo>,
+}
+
+/// Iterator over the solutions of a grid, found by the same search as
+/// `BacktrackingStrategy` with an explicit stack. Every solution holds the values of
+/// all slots, indexed by `id - 1`, and at most `limit` of them are produced.
+pub struct Solutions {
+    state: BacktrackState,
+    stack: Vec<Frame>,
+    started: bool,
+    remaining: usize,
+}
+
+impl Solutions {
+    pub fn new<S: SimpleSudoku>(sudoku: &S, limit: usize) -> Self {
+        Self {
+            state: BacktrackState::from_sudoku(sudoku),
+            stack: Vec::new(),
+            started: false,
+            remaining: limit,
+        }
+    }
+
+    /// Stop producing solutions once the budget runs out. Every placement is a node.
+    pub fn within(mut self, budget: &Budget) -> Self {
+        self.state.budget = Some(budget.clone());
+        self
+    }
+
+    /// Branch on the next slot; returns the solution if there is nothing left to fill.
+    fn descend(&mut self) -> Option<Vec<u8>> {
+        let Some(alternatives) = self.state.branch() else {
+            self.remaining -= 1;
+            return Some(self.state.values.iter().map(|v| v.unwrap_or(0)).collect());
+        };
+        self.stack.push(Frame {
+            alternatives,
+            next: 0,
+            undo: None,
+        });
+        None
+    }
+}
+
+impl Iterator for Solutions {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            if let Some(solution) = self.descend() {
+                // Nothing to search: the grid is already filled.
+                self.remaining = 0;
+                return Some(solution);
+            }
+        }
+        loop {
+            if self.state.interrupted() {
+                self.remaining = 0;
+                return None;
+            }
+            let frame = self.stack.last_mut()?;
+            let undo = frame.undo.take();
+            let (idx, value) = match frame.alternatives.get(frame.next) {
+                Some(&alternative) => alternative,
+                None => {
+                    self.stack.pop();
+                    if let Some(undo) = undo {
+                        self.state.undo(undo);
+                    }
+                    continue;
+                }
+            };
+            frame.next += 1;
+            if let Some(undo) = undo {
+                self.state.undo(undo);
+            }
+            if let Some(budget) = &self.state.budget {
+                budget.spend(1);
+            }
+            let (undo, emptied) = self.state.place(idx, value);
+            self.stack
+                .last_mut()
+                .expect("Frame is still on the stack.")
+                .undo = Some(undo);
+            if emptied.is_some() {
+                continue;
+            }
+            if let Some(solution) = self.descend() {
+                return Some(solution);
+            }
+        }
+    }
+}
+
+/// Iterator over at most `limit` solutions of the grid, as values of every slot
+/// indexed by `id - 1`. The grid itself is left untouched.
+pub fn solutions<S: SimpleSudoku>(sudoku: &S, limit: usize) -> Solutions {
+    Solutions::new(sudoku, limit)
+}
+
+/// `solutions`, ending early once the budget runs out.
+pub fn solutions_within<S: SimpleSudoku>(sudoku: &S, limit: usize, budget: &Budget) -> Solutions {
+    Solutions::new(sudoku, limit).within(budget)
+}
+
+/// Number of solutions, counting stops at `limit` (e.g. 2 to check uniqueness).
+pub fn count_solutions<S: SimpleSudoku>(sudoku: &S, limit: usize) -> usize {
+    solutions(sudoku, limit).count()
+}
+
+/// `count_solutions`, counting only the solutions found before the budget ran out.
+pub fn count_solutions_within<S: SimpleSudoku>(sudoku: &S, limit: usize, budget: &Budget) -> usize {
+    solutions_within(sudoku, limit, budget).count()
+}
+
 impl<S: SimpleSudoku> Strategy<S> for BacktrackingStrategy {
     fn run(&self, sudoku: &mut S) {
+        self.run_within(sudoku, &Budget::unlimited());
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        self.run_observed(sudoku, budget, &mut ());
+    }
+
+    fn run_observed(&self, sudoku: &mut S, budget: &Budget, observer: &mut dyn SolverObserver) {
         info!("BacktrackingStrategy: started.");
         let unsolved_count = sudoku
             .slots()
@@ -183,11 +674,391 @@ impl<S: SimpleSudoku> Strategy<S> for BacktrackingStrategy {
         );
 
         let mut state = BacktrackState::from_sudoku(sudoku);
-        if solve(&mut state, 0) {
+        state.propagation = self.propagation;
+        state.variable_order = self.variable_order;
+        state.value_order = self.value_order;
+        if let ValueOrder::Random(seed) = self.value_order {
+            state.rng = seed;
+        }
+        state.budget = Some(budget.clone());
+        let mut solved = false;
+        if state.propagate() {
+            let mut visit = |_: &[Option<u8>]| {
+                solved = true;
+                true
+            };
+            solve(&mut state, 0, &mut visit, observer);
+        }
+        self.stats.set(state.stats);
+        info!(
+            "BacktrackingStrategy: {} nodes, {} backtracks.",
+            state.stats.nodes, state.stats.backtracks
+        );
+        if solved {
             state.apply_solution(sudoku);
             debug!("BacktrackingStrategy: Successfully solved the puzzle.");
+        } else if let Some(interruption) = budget.check() {
+            warn!("BacktrackingStrategy: Stopped early: {:?}.", interruption);
         } else {
             error!("BacktrackingStrategy: No solution found.");
         }
     }
 }
+
+/// Solutions and per-thread effort of a parallel search.
+#[derive(Debug, Default)]
+pub struct ParallelSearch {
+    /// Values of all slots of every solution found, indexed by `id - 1`.
+    pub solutions: Vec<Vec<u8>>,
+    /// Search effort of every worker thread.
+    pub thread_stats: Vec<SearchStats>,
+}
+
+/// Backtracking for large grids: the top `split_depth` levels of the tree are
+/// expanded up front, and the subtrees are shared out to `threads` workers. The
+/// remaining work is cancelled once enough solutions are found.
+#[derive(Debug)]
+pub struct ParallelBacktrackingStrategy {
+    pub threads: usize,
+    pub split_depth: usize,
+    pub propagation: Propagation,
+    thread_stats: RefCell<Vec<SearchStats>>,
+}
+
+impl Default for ParallelBacktrackingStrategy {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(4, |threads| threads.get()),
+            split_depth: 2,
+            propagation: Propagation::Singles,
+            thread_stats: RefCell::default(),
+        }
+    }
+}
+
+impl Display for ParallelBacktrackingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ParallelBacktrackingStrategy")
+    }
+}
+
+impl ParallelBacktrackingStrategy {
+    /// Node and backtrack counters of every worker in the last run.
+    pub fn thread_stats(&self) -> Vec<SearchStats> {
+        self.thread_stats.borrow().clone()
+    }
+
+    /// Subproblems left after expanding `split_depth` levels of the tree. Branches
+    /// are exclusive, so no solution is found twice.
+    fn split(&self, root: BacktrackState) -> VecDeque<BacktrackState> {
+        let mut frontier = VecDeque::from([root]);
+        for _ in 0..self.split_depth {
+            let mut next = VecDeque::new();
+            for mut state in frontier {
+                let Some(alternatives) = state.branch() else {
+                    // Already solved, the worker only has to report it.
+                    next.push_back(state);
+                    continue;
+                };
+                for (idx, value) in alternatives {
+                    let mut child = state.clone();
+                    if child.assign(idx, value).is_none() && child.propagate() {
+                        next.push_back(child);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        frontier
+    }
+
+    /// At most `limit` solutions of the grid, searched in parallel.
+    pub fn solutions<S: SimpleSudoku>(&self, sudoku: &S, limit: usize) -> ParallelSearch {
+        self.solutions_within(sudoku, limit, &Budget::unlimited())
+    }
+
+    /// `solutions`, stopping all workers once the budget runs out.
+    pub fn solutions_within<S: SimpleSudoku>(
+        &self,
+        sudoku: &S,
+        limit: usize,
+        budget: &Budget,
+    ) -> ParallelSearch {
+        let mut root = BacktrackState::from_sudoku(sudoku);
+        root.propagation = self.propagation;
+        if limit == 0 || !root.propagate() {
+            return ParallelSearch::default();
+        }
+        let queue = Mutex::new(self.split(root));
+        let stop = Arc::new(AtomicBool::new(false));
+        let solutions = Mutex::new(Vec::new());
+
+        let thread_stats = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.max(1))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut stats = SearchStats::default();
+                        while !stop.load(Ordering::Relaxed) && budget.check().is_none() {
+                            let Some(mut state) = queue.lock().expect("Queue lock.").pop_front()
+                            else {
+                                break;
+                            };
+                            state.stop = Some(Arc::clone(&stop));
+                            state.budget = Some(budget.clone());
+                            let mut visit = |values: &[Option<u8>]| {
+                                let mut solutions = solutions.lock().expect("Solutions lock.");
+                                if solutions.len() < limit {
+                                    solutions.push(values.iter().map(|v| v.unwrap_or(0)).collect());
+                                }
+                                if solutions.len() >= limit {
+                                    stop.store(true, Ordering::Relaxed);
+                                }
+                                solutions.len() >= limit
+                            };
+                            // Workers run concurrently, so guesses aren't reported.
+                            solve(&mut state, 0, &mut visit, &mut ());
+                            stats.nodes += state.stats.nodes;
+                            stats.backtracks += state.stats.backtracks;
+                        }
+                        stats
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("Worker panicked."))
+                .collect()
+        });
+        ParallelSearch {
+            solutions: solutions.into_inner().expect("Solutions lock."),
+            thread_stats,
+        }
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for ParallelBacktrackingStrategy {
+    fn run(&self, sudoku: &mut S) {
+        self.run_within(sudoku, &Budget::unlimited());
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        info!("{}: started with {} threads.", self, self.threads);
+        let search = self.solutions_within(sudoku, 1, budget);
+        for (thread, stats) in search.thread_stats.iter().enumerate() {
+            info!(
+                "{}: thread {} searched {} nodes, {} backtracks.",
+                self, thread, stats.nodes, stats.backtracks
+            );
+        }
+        *self.thread_stats.borrow_mut() = search.thread_stats;
+        let Some(solution) = search.solutions.first() else {
+            match budget.check() {
+                Some(interruption) => warn!("{}: Stopped early: {:?}.", self, interruption),
+                None => error!("{}: No solution found.", self),
+            }
+            return;
+        };
+        for slot in sudoku.slots() {
+            let mut slot = slot.borrow_mut();
+            if !slot.is_solved() {
+                let value = solution[slot.id() - 1];
+                slot.set_value(Some(value));
+            }
+        }
+        debug!("{}: Successfully solved the puzzle.", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::budget::CancellationToken;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::traits::{HasSlots, Solvable};
+    use rstest::rstest;
+
+    fn solution_values(filename: &str) -> Vec<u8> {
+        load_csv(filename)
+            .into_iter()
+            .flatten()
+            .map(|value| value.expect("Solution is complete."))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::easy("fixtures/easy.csv", "fixtures/easy_solved.csv")]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    #[case::hard_2("fixtures/hard_2.csv", "fixtures/hard_2_solved.csv")]
+    fn test_unique_solution(#[case] puzzle: &str, #[case] solution: &str) {
+        let sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let solutions: Vec<Vec<u8>> = solutions(&sudoku, 10).collect();
+        assert_eq!(solutions, vec![solution_values(solution)]);
+        assert_eq!(count_solutions(&sudoku, 10), 1);
+    }
+
+    #[test]
+    fn test_count_solutions_with_limit() {
+        // r1c1 r1c9 r2c1 r2c9 hold 2 and 4 crosswise over areas 1 and 3.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        for (row, column) in [(0, 0), (0, 8), (1, 0), (1, 8)] {
+            grid[row][column] = None;
+        }
+        let sudoku = Sudoku9x9::new(grid);
+        let solutions: Vec<Vec<u8>> = solutions(&sudoku, 10).collect();
+        assert_eq!(solutions.len(), 2);
+        assert_ne!(solutions[0], solutions[1]);
+        assert_eq!(count_solutions(&sudoku, 1), 1);
+
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        assert_eq!(count_solutions(&empty, 50), 50);
+        assert_eq!(count_solutions(&empty, 0), 0);
+    }
+
+    #[test]
+    fn test_count_solutions_within_budget() {
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        assert_eq!(count_solutions_within(&empty, 50, &Budget::unlimited()), 50);
+
+        // The first solution alone takes 81 placements.
+        let budget = Budget::unlimited().with_node_limit(40);
+        assert_eq!(count_solutions_within(&empty, 50, &budget), 0);
+        assert_eq!(budget.nodes(), 41);
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let budget = Budget::unlimited().with_cancellation(token);
+        assert_eq!(count_solutions_within(&empty, 50, &budget), 0);
+    }
+
+    #[test]
+    fn test_solved_and_invalid_grids() {
+        let solved = Sudoku9x9::new(load_csv("fixtures/hard_1_solved.csv"));
+        assert_eq!(count_solutions(&solved, 10), 1);
+
+        // r1c1 is 2 in the only solution.
+        let mut grid = load_csv("fixtures/hard_1.csv");
+        grid[0][0] = Some(5);
+        assert_eq!(count_solutions(&Sudoku9x9::new(grid), 10), 0);
+    }
+
+    fn values(sudoku: &Sudoku9x9) -> Vec<u8> {
+        sudoku
+            .slots()
+            .iter()
+            .map(|slot| slot.borrow().get_value().unwrap_or(0))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    #[case::hard_2("fixtures/hard_2.csv", "fixtures/hard_2_solved.csv")]
+    fn test_propagation_levels(#[case] puzzle: &str, #[case] solution: &str) {
+        let mut nodes = Vec::new();
+        for propagation in [
+            Propagation::Peers,
+            Propagation::NakedSingles,
+            Propagation::Singles,
+            Propagation::Subsets,
+        ] {
+            let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+            let strategy = BacktrackingStrategy::with_propagation(propagation);
+            strategy.run(&mut sudoku);
+            assert_eq!(values(&sudoku), solution_values(solution));
+            let stats = strategy.stats();
+            assert!(stats.backtracks <= stats.nodes);
+            nodes.push(stats.nodes);
+        }
+        // Deducing more at every node never needs more guesses than peers only.
+        assert!(nodes[2] <= nodes[0]);
+        assert!(nodes[3] <= nodes[2]);
+    }
+
+    #[test]
+    fn test_propagation_detects_invalid_grid() {
+        let mut grid = load_csv("fixtures/hard_1.csv");
+        grid[0][0] = Some(5);
+        let mut sudoku = Sudoku9x9::new(grid);
+        let strategy = BacktrackingStrategy::with_propagation(Propagation::Singles);
+        strategy.run(&mut sudoku);
+        assert!(!sudoku.is_solved());
+    }
+
+    #[rstest]
+    #[case::mrv(VariableOrder::Mrv, ValueOrder::Ascending)]
+    #[case::mrv_degree(VariableOrder::MrvDegree, ValueOrder::LeastConstraining)]
+    #[case::unit_digit(VariableOrder::UnitDigit, ValueOrder::Ascending)]
+    #[case::random(VariableOrder::UnitDigit, ValueOrder::Random(42))]
+    fn test_orderings(#[case] variable_order: VariableOrder, #[case] value_order: ValueOrder) {
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        let strategy = BacktrackingStrategy::default()
+            .with_variable_order(variable_order)
+            .with_value_order(value_order);
+        strategy.run(&mut sudoku);
+        assert_eq!(
+            values(&sudoku),
+            solution_values("fixtures/hard_2_solved.csv")
+        );
+    }
+
+    #[test]
+    fn test_random_order_is_deterministic_per_seed() {
+        // The empty grid has many solutions, the one found depends on the seed only.
+        let solve = |seed: u64| {
+            let mut sudoku = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+            let strategy =
+                BacktrackingStrategy::default().with_value_order(ValueOrder::Random(seed));
+            strategy.run(&mut sudoku);
+            assert!(sudoku.is_solved());
+            (values(&sudoku), strategy.stats())
+        };
+        assert_eq!(solve(7), solve(7));
+        assert_ne!(solve(7).0, solve(8).0);
+    }
+
+    #[rstest]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    #[case::hard_2("fixtures/hard_2.csv", "fixtures/hard_2_solved.csv")]
+    fn test_parallel_backtracking(#[case] puzzle: &str, #[case] solution: &str) {
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let strategy = ParallelBacktrackingStrategy {
+            threads: 3,
+            ..ParallelBacktrackingStrategy::default()
+        };
+        strategy.run(&mut sudoku);
+        assert_eq!(values(&sudoku), solution_values(solution));
+        assert_eq!(strategy.thread_stats().len(), 3);
+    }
+
+    #[test]
+    fn test_parallel_solutions_with_limit() {
+        let strategy = ParallelBacktrackingStrategy {
+            threads: 4,
+            split_depth: 3,
+            propagation: Propagation::Peers,
+            ..ParallelBacktrackingStrategy::default()
+        };
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        let search = strategy.solutions(&empty, 20);
+        assert_eq!(search.solutions.len(), 20);
+        let distinct: HashSet<&Vec<u8>> = search.solutions.iter().collect();
+        assert_eq!(distinct.len(), 20);
+        assert!(
+            search
+                .thread_stats
+                .iter()
+                .map(|stats| stats.nodes)
+                .sum::<usize>()
+                > 0
+        );
+
+        // r1c1 r1c9 r2c1 r2c9 hold 2 and 4 crosswise over areas 1 and 3.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        for (row, column) in [(0, 0), (0, 8), (1, 0), (1, 8)] {
+            grid[row][column] = None;
+        }
+        let search = strategy.solutions(&Sudoku9x9::new(grid), 10);
+        assert_eq!(search.solutions.len(), 2);
+        assert!(strategy.solutions(&empty, 0).solutions.is_empty());
+    }
+}
@@ -0,0 +1,554 @@
+use crate::helpers::{peer_ids, position, run_deductions, run_found_deductions};
+use crate::objects::budget::Budget;
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+
+/// Which links a chain may use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChainKind {
+    /// Single digit, unit links only.
+    X,
+    /// Strong links inside bivalue cells, weak links between cells.
+    XY,
+    /// Any strong and weak links.
+    Alternating,
+}
+
+/// Candidate `value` of slot `id`, a node of the chain.
+type Node = (usize, u8);
+
+/// Strong and weak links between the candidates of unsolved slots.
+struct LinkGraph {
+    nodes: Vec<Node>,
+    index: HashMap<Node, usize>,
+    strong: Vec<Vec<usize>>,
+    weak: Vec<Vec<usize>>,
+    candidates: HashMap<usize, Vec<u8>>,
+    peers: HashMap<usize, HashSet<usize>>,
+    positions: HashMap<usize, (usize, usize)>,
+}
+
+impl LinkGraph {
+    fn new<S: SimpleSudoku>(sudoku: &S, kind: ChainKind) -> Self {
+        let mut candidates = HashMap::new();
+        let mut peers = HashMap::new();
+        let mut positions = HashMap::new();
+        let mut nodes = Vec::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            if slot.is_solved() {
+                continue;
+            }
+            let mut values: Vec<u8> = slot.candidates().into_iter().collect();
+            values.sort_unstable();
+            nodes.extend(values.iter().map(|&value| (slot.id(), value)));
+            let (row, column, _) = position(&slot);
+            candidates.insert(slot.id(), values);
+            peers.insert(slot.id(), peer_ids(&slot));
+            positions.insert(slot.id(), (row, column));
+        }
+        let index: HashMap<Node, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, &node)| (node, idx))
+            .collect();
+
+        let mut graph = LinkGraph {
+            strong: vec![Vec::new(); nodes.len()],
+            weak: vec![Vec::new(); nodes.len()],
+            nodes,
+            index,
+            candidates,
+            peers,
+            positions,
+        };
+
+        if kind != ChainKind::X {
+            let mut ids: Vec<usize> = graph.candidates.keys().copied().collect();
+            ids.sort_unstable();
+            for id in ids {
+                let values = graph.candidates[&id].clone();
+                for (idx, &first) in values.iter().enumerate() {
+                    for &second in &values[idx + 1..] {
+                        let (a, b) = (graph.index[&(id, first)], graph.index[&(id, second)]);
+                        if values.len() == 2 {
+                            graph.link(a, b, true);
+                        }
+                        if kind == ChainKind::Alternating {
+                            graph.link(a, b, false);
+                        }
+                    }
+                }
+            }
+        }
+
+        for unit in sudoku.units() {
+            for value in 1..=S::LENGTH as u8 {
+                let members: Vec<usize> = unit
+                    .iter()
+                    .map(|slot| slot.borrow().id())
+                    .filter_map(|id| graph.index.get(&(id, value)).copied())
+                    .collect();
+                if members.len() == 2 && kind != ChainKind::XY {
+                    graph.link(members[0], members[1], true);
+                }
+                for (idx, &first) in members.iter().enumerate() {
+                    for &second in &members[idx + 1..] {
+                        graph.link(first, second, false);
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    fn link(&mut self, a: usize, b: usize, strong: bool) {
+        let links = if strong {
+            &mut self.strong
+        } else {
+            &mut self.weak
+        };
+        if !links[a].contains(&b) {
+            links[a].push(b);
+            links[b].push(a);
+        }
+    }
+
+    fn is_weak(&self, a: usize, b: usize) -> bool {
+        self.weak[a].contains(&b)
+    }
+
+    fn name(&self, node: usize) -> String {
+        let (id, value) = self.nodes[node];
+        let (row, column) = self.positions[&id];
+        format!("({value})r{row}c{column}")
+    }
+
+    /// Chain in Eureka notation, `=` for strong and `-` for weak links.
+    fn eureka(&self, chain: &[usize], closed: bool) -> String {
+        let mut text = self.name(chain[0]);
+        for (idx, &node) in chain.iter().enumerate().skip(1) {
+            text.push(if idx % 2 == 1 { '=' } else { '-' });
+            text.push_str(&self.name(node));
+        }
+        if closed {
+            text.push('-');
+            text.push_str(&self.name(chain[0]));
+        }
+        text
+    }
+
+    /// Cells with `value` seeing both slots, except the ones used by the chain.
+    fn seen_by_both(&self, first: usize, second: usize, value: u8, chain: &[usize]) -> Vec<usize> {
+        let mut targets: Vec<usize> = self
+            .candidates
+            .iter()
+            .filter(|(id, values)| {
+                values.contains(&value)
+                    && !chain.contains(&self.index[&(**id, value)])
+                    && self.peers[id].contains(&first)
+                    && self.peers[id].contains(&second)
+            })
+            .map(|(&id, _)| id)
+            .collect();
+        targets.sort_unstable();
+        targets
+    }
+
+    /// Eliminations allowed by a weak link once it is known that one end is true.
+    fn weak_link_eliminations(&self, a: usize, b: usize, chain: &[usize]) -> Vec<Node> {
+        let ((first, x), (second, y)) = (self.nodes[a], self.nodes[b]);
+        if first == second {
+            return self.candidates[&first]
+                .iter()
+                .filter(|&&value| {
+                    value != x && value != y && !chain.contains(&self.index[&(first, value)])
+                })
+                .map(|&value| (first, value))
+                .collect();
+        }
+        if x == y {
+            return self
+                .seen_by_both(first, second, x, chain)
+                .into_iter()
+                .map(|id| (id, x))
+                .collect();
+        }
+        // Different cells and digits: each end removes its digit from the other cell.
+        let mut eliminations = Vec::new();
+        if self.peers[&first].contains(&second) {
+            if self.candidates[&first].contains(&y) {
+                eliminations.push((first, y));
+            }
+            if self.candidates[&second].contains(&x) {
+                eliminations.push((second, x));
+            }
+        }
+        eliminations
+    }
+
+    /// Shortest alternating chains starting with a strong link from `start`.
+    /// Node `start` is assumed false, every chain ends with a node forced true.
+    fn chains_from(&self, start: usize, max_length: usize) -> Vec<Vec<usize>> {
+        // State is (node, is_true); the parent map rebuilds the path.
+        let mut parents: HashMap<(usize, bool), (usize, bool)> = HashMap::new();
+        let mut queue = VecDeque::from([((start, false), 1)]);
+        let mut chains = Vec::new();
+        while let Some(((node, value), length)) = queue.pop_front() {
+            if length >= max_length {
+                continue;
+            }
+            let next = if value {
+                &self.weak[node]
+            } else {
+                &self.strong[node]
+            };
+            for &other in next {
+                let state = (other, !value);
+                if state == (start, false) || parents.contains_key(&state) {
+                    continue;
+                }
+                parents.insert(state, (node, value));
+                if !value && length + 1 >= 4 {
+                    let mut chain = vec![other];
+                    let mut current = (node, value);
+                    while current != (start, false) {
+                        chain.push(current.0);
+                        current = parents[&current];
+                    }
+                    chain.push(start);
+                    chain.reverse();
+                    chains.push(chain);
+                }
+                queue.push_back((state, length + 1));
+            }
+        }
+        chains
+    }
+}
+
+/// Chains of the kind found before the budget runs out, checked before every start node.
+fn chain_deductions<S: SimpleSudoku>(
+    sudoku: &S,
+    kind: ChainKind,
+    technique: &'static str,
+    max_length: usize,
+    budget: &Budget,
+) -> Vec<Deduction> {
+    let graph = LinkGraph::new(sudoku, kind);
+    let mut found: Vec<(Vec<usize>, Deduction)> = Vec::new();
+
+    for start in 0..graph.nodes.len() {
+        if budget.check().is_some() {
+            break;
+        }
+        for chain in graph.chains_from(start, max_length) {
+            let end = *chain.last().unwrap();
+            let inner = &chain[..chain.len() - 1];
+            let distinct: HashSet<&usize> = inner.iter().collect();
+            if distinct.len() != inner.len() {
+                continue;
+            }
+
+            let cells: Vec<usize> = chain.iter().fold(Vec::new(), |mut cells, &node| {
+                let id = graph.nodes[node].0;
+                if cells.last() != Some(&id) {
+                    cells.push(id);
+                }
+                cells
+            });
+
+            if end == start {
+                // Assuming the start false forces it true: a discontinuous nice loop.
+                if kind == ChainKind::Alternating && inner.len() >= 4 {
+                    let (id, value) = graph.nodes[start];
+                    let mut deduction = Deduction::new("Discontinuous Nice Loop", cells);
+                    deduction.place(id, value);
+                    deduction.set_explanation(graph.eureka(&chain, false));
+                    found.push((chain, deduction));
+                }
+                continue;
+            }
+            if chain[1..].contains(&start) {
+                continue;
+            }
+
+            let closed = kind == ChainKind::Alternating && graph.is_weak(end, start);
+            let (name, eliminations) = if closed {
+                // Every weak link of a continuous loop turns into a strong one.
+                let mut eliminations = Vec::new();
+                let mut weak_links: Vec<(usize, usize)> = chain[1..]
+                    .chunks(2)
+                    .filter(|pair| pair.len() == 2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .collect();
+                weak_links.push((end, start));
+                for (a, b) in weak_links {
+                    eliminations.extend(graph.weak_link_eliminations(a, b, &chain));
+                }
+                ("Continuous Nice Loop", eliminations)
+            } else {
+                // Ends in one cell are always weakly linked, so this is an open chain.
+                (technique, graph.weak_link_eliminations(start, end, &chain))
+            };
+            if eliminations.is_empty() {
+                continue;
+            }
+            let mut deduction = Deduction::new(name, cells);
+            for (id, value) in eliminations {
+                deduction.eliminate(id, value);
+            }
+            deduction.set_explanation(graph.eureka(&chain, closed));
+            found.push((chain, deduction));
+        }
+    }
+
+    // Shortest chains first, dropping the ones adding nothing new.
+    found.sort_by_key(|(chain, _)| chain.len());
+    let mut seen: HashSet<(usize, u8)> = HashSet::new();
+    let mut deductions = Vec::new();
+    for (_, deduction) in found {
+        let new_placements = deduction.placements().iter().any(|p| !seen.contains(p));
+        let new_eliminations = deduction.eliminations().iter().any(|e| !seen.contains(e));
+        if !new_placements && !new_eliminations {
+            continue;
+        }
+        seen.extend(deduction.placements().iter().copied());
+        seen.extend(deduction.eliminations().iter().copied());
+        deductions.push(deduction);
+    }
+    deductions
+}
+
+/// Alternating chain of conjugate pairs and weak links of a single digit.
+/// The digit is removed from cells seeing both ends.
+#[derive(Debug)]
+pub struct XChainStrategy {
+    pub max_length: usize,
+}
+
+impl Default for XChainStrategy {
+    fn default() -> Self {
+        Self { max_length: 12 }
+    }
+}
+
+impl Display for XChainStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XChainStrategy")
+    }
+}
+
+impl XChainStrategy {
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        chain_deductions(sudoku, ChainKind::X, "X-Chain", self.max_length, budget)
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for XChainStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["X-Chain"]
+    }
+}
+
+/// Chain of bivalue cells, each sharing a digit with the next one.
+/// The digit at both ends is removed from cells seeing them.
+#[derive(Debug)]
+pub struct XYChainStrategy {
+    pub max_length: usize,
+}
+
+impl Default for XYChainStrategy {
+    fn default() -> Self {
+        Self { max_length: 16 }
+    }
+}
+
+impl Display for XYChainStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XYChainStrategy")
+    }
+}
+
+impl XYChainStrategy {
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        chain_deductions(sudoku, ChainKind::XY, "XY-Chain", self.max_length, budget)
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for XYChainStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["XY-Chain"]
+    }
+}
+
+/// Alternating inference chains over any strong (bivalue cell, conjugate pair) and
+/// weak (cell, unit) links, including continuous and discontinuous nice loops.
+/// The chain is reported in Eureka notation.
+#[derive(Debug)]
+pub struct AlternatingInferenceChainStrategy {
+    pub max_length: usize,
+}
+
+impl Default for AlternatingInferenceChainStrategy {
+    fn default() -> Self {
+        Self { max_length: 12 }
+    }
+}
+
+impl Display for AlternatingInferenceChainStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AlternatingInferenceChainStrategy")
+    }
+}
+
+impl AlternatingInferenceChainStrategy {
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        chain_deductions(
+            sudoku,
+            ChainKind::Alternating,
+            "AIC",
+            self.max_length,
+            budget,
+        )
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for AlternatingInferenceChainStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["AIC", "Discontinuous Nice Loop", "Continuous Nice Loop"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::{WITHOUT_ONE, with_candidates, with_ones};
+    use crate::objects::sudoku::Sudoku9x9;
+
+    #[test]
+    fn test_xy_chain() {
+        // r1c1 {1,2} - r1c5 {2,3} - r5c5 {3,1}: 1 is removed from r5c1.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (5, &[2, 3]), (41, &[1, 3])]);
+        let deductions = XYChainStrategy::default().deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.eliminations().contains(&(37, 1)))
+            .expect("XY-Chain is not found.");
+        assert_eq!(found.technique(), "XY-Chain");
+        assert_eq!(
+            found.explanation(),
+            Some("(1)r1c1=(2)r1c1-(2)r1c5=(3)r1c5-(3)r5c5=(1)r5c5")
+        );
+    }
+
+    #[test]
+    fn test_x_chain() {
+        // Conjugate pairs in rows 1, 5 and 9 joined through columns 5 and 9.
+        let sudoku = with_ones(&[1, 5, 41, 45, 81, 74, 55, 64]);
+        let deductions = XChainStrategy::default().deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique(), "X-Chain");
+        assert_eq!(
+            deductions[0].explanation(),
+            Some("(1)r1c1=(1)r1c5-(1)r5c5=(1)r5c9-(1)r9c9=(1)r9c2")
+        );
+        assert_eq!(deductions[0].eliminations(), &[(55, 1), (64, 1)]);
+    }
+
+    /// r1c1 {13}, r1c5 {23}, r5c5 {12}, and column 1 holds 1 only in r1c1 and r5c1.
+    fn nice_loop_grid() -> Sudoku9x9 {
+        let mut cells: Vec<(usize, &[u8])> = vec![(1, &[1, 3]), (5, &[2, 3]), (41, &[1, 2])];
+        cells.extend([10, 19, 28, 46, 55, 64, 73].map(|id| (id, WITHOUT_ONE)));
+        with_candidates(&cells)
+    }
+
+    #[test]
+    fn test_aic() {
+        // Mixes the column 1 conjugate pair with bivalue cells: r1c1 is 1 or r1c5 is 3.
+        let deductions = AlternatingInferenceChainStrategy::default().deductions(&nice_loop_grid());
+        let found = deductions
+            .iter()
+            .find(|d| d.technique() == "AIC" && d.eliminations() == [(1, 3)])
+            .expect("AIC is not found.");
+        assert_eq!(
+            found.explanation(),
+            Some("(1)r1c1=(1)r5c1-(1)r5c5=(2)r5c5-(2)r1c5=(3)r1c5")
+        );
+    }
+
+    #[test]
+    fn test_discontinuous_nice_loop() {
+        // Assuming r1c1 is not 1 leads back to r1c1 being 1.
+        let deductions = AlternatingInferenceChainStrategy::default().deductions(&nice_loop_grid());
+        let found = deductions
+            .iter()
+            .find(|d| d.technique() == "Discontinuous Nice Loop")
+            .expect("Discontinuous Nice Loop is not found.");
+        assert_eq!(found.placements(), &[(1, 1)]);
+        assert_eq!(
+            found.explanation(),
+            Some("(1)r1c1=(3)r1c1-(3)r1c5=(2)r1c5-(2)r5c5=(1)r5c5-(1)r5c1=(1)r1c1")
+        );
+    }
+
+    #[test]
+    fn test_continuous_nice_loop() {
+        // Bivalue cells r1c1 {12}, r1c5 {23}, r5c5 {34} and r5c1 {41} close a loop:
+        // every weak link becomes strong and locks its digit in its unit.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (5, &[2, 3]), (41, &[3, 4]), (37, &[1, 4])]);
+        let deductions = AlternatingInferenceChainStrategy::default().deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique(), "Continuous Nice Loop");
+        assert_eq!(
+            deductions[0].explanation(),
+            Some("(1)r1c1=(2)r1c1-(2)r1c5=(3)r1c5-(3)r5c5=(4)r5c5-(4)r5c1=(1)r5c1-(1)r1c1")
+        );
+        let expected: Vec<(usize, u8)> = [2, 3, 4, 6, 7, 8, 9]
+            .map(|id| (id, 2))
+            .into_iter()
+            .chain([14, 23, 32, 50, 59, 68, 77].map(|id| (id, 3)))
+            .chain([38, 39, 40, 42, 43, 44, 45].map(|id| (id, 4)))
+            .chain([10, 19, 28, 46, 55, 64, 73].map(|id| (id, 1)))
+            .collect();
+        assert_eq!(deductions[0].eliminations(), expected);
+    }
+}
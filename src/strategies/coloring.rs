@@ -0,0 +1,258 @@
+use crate::helpers::run_deductions;
+use crate::objects::deduction::Deduction;
+use crate::strategies::single_digit::DigitMap;
+use crate::traits::{SimpleSudoku, Strategy};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+
+/// Connected group of conjugate pairs, two-colored: exactly one color holds the digit.
+struct Cluster {
+    colors: [Vec<usize>; 2],
+}
+
+impl Cluster {
+    fn contains(&self, id: usize) -> bool {
+        self.colors.iter().any(|color| color.contains(&id))
+    }
+
+    /// Cells of both colors, the first color leading.
+    fn cells(&self) -> Vec<usize> {
+        self.colors.concat()
+    }
+}
+
+fn clusters(map: &DigitMap) -> Vec<Cluster> {
+    let mut neighbours: HashMap<usize, Vec<usize>> = HashMap::new();
+    for link in &map.links {
+        let (first, second) = link.ends;
+        neighbours.entry(first).or_default().push(second);
+        neighbours.entry(second).or_default().push(first);
+    }
+    let mut starts: Vec<usize> = neighbours.keys().copied().collect();
+    starts.sort_unstable();
+
+    let mut colored: HashMap<usize, usize> = HashMap::new();
+    let mut clusters = Vec::new();
+    for start in starts {
+        if colored.contains_key(&start) {
+            continue;
+        }
+        let mut cluster = Cluster {
+            colors: [Vec::new(), Vec::new()],
+        };
+        let mut queue = VecDeque::from([(start, 0)]);
+        colored.insert(start, 0);
+        while let Some((id, color)) = queue.pop_front() {
+            cluster.colors[color].push(id);
+            for &next in &neighbours[&id] {
+                if let Entry::Vacant(entry) = colored.entry(next) {
+                    entry.insert(1 - color);
+                    queue.push_back((next, 1 - color));
+                }
+            }
+        }
+        for color in cluster.colors.iter_mut() {
+            color.sort_unstable();
+        }
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+/// Does any cell of `first` see any cell of `second`.
+fn sees_any(map: &DigitMap, first: &[usize], second: &[usize]) -> bool {
+    first.iter().any(|id| {
+        second
+            .iter()
+            .any(|other| map.cells[id].peers.contains(other))
+    })
+}
+
+/// Cells outside the clusters seeing a cell of `first` and a cell of `second`.
+fn trapped(map: &DigitMap, first: &[usize], second: &[usize], clusters: &[&Cluster]) -> Vec<usize> {
+    let mut targets: Vec<usize> = map
+        .cells
+        .iter()
+        .filter(|(id, cell)| {
+            !clusters.iter().any(|cluster| cluster.contains(**id))
+                && first.iter().any(|other| cell.peers.contains(other))
+                && second.iter().any(|other| cell.peers.contains(other))
+        })
+        .map(|(&id, _)| id)
+        .collect();
+    targets.sort_unstable();
+    targets
+}
+
+/// Two-colors every cluster of conjugate pairs of a digit.
+/// Color wrap: two cells of the same color see each other, so that color is false.
+/// Color trap: a cell seeing both colors can't hold the digit.
+#[derive(Debug)]
+pub struct SimpleColoringStrategy;
+
+impl Display for SimpleColoringStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SimpleColoringStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for SimpleColoringStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        for value in 1..=S::LENGTH as u8 {
+            let map = DigitMap::new(sudoku, value);
+            for cluster in clusters(&map) {
+                let mut wrapped = false;
+                for color in &cluster.colors {
+                    let sees_itself = color.iter().any(|id| {
+                        color
+                            .iter()
+                            .any(|other| map.cells[id].peers.contains(other))
+                    });
+                    if sees_itself {
+                        let mut deduction = Deduction::new("Color Wrap", cluster.cells());
+                        for &id in color {
+                            deduction.eliminate(id, value);
+                        }
+                        deductions.push(deduction);
+                        wrapped = true;
+                    }
+                }
+                if wrapped {
+                    continue;
+                }
+
+                let targets = trapped(&map, &cluster.colors[0], &cluster.colors[1], &[&cluster]);
+                if !targets.is_empty() {
+                    let mut deduction = Deduction::new("Color Trap", cluster.cells());
+                    for target in targets {
+                        deduction.eliminate(target, value);
+                    }
+                    deductions.push(deduction);
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Color Wrap", "Color Trap"]
+    }
+}
+
+/// Colors clusters of conjugate pairs of a digit and combines pairs of clusters.
+/// If a color of one cluster sees both colors of another one, it is false.
+/// If color `a1` sees color `b1`, then `a2` or `b2` is true and cells seeing both
+/// can't hold the digit.
+#[derive(Debug)]
+pub struct MultiColoringStrategy;
+
+impl Display for MultiColoringStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MultiColoringStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for MultiColoringStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        for value in 1..=S::LENGTH as u8 {
+            let map = DigitMap::new(sudoku, value);
+            let clusters = clusters(&map);
+            for (first_idx, first) in clusters.iter().enumerate() {
+                for (second_idx, second) in clusters.iter().enumerate() {
+                    if first_idx == second_idx {
+                        continue;
+                    }
+                    let mut cells = first.cells();
+                    cells.extend(second.cells());
+
+                    for (color, other) in [(0, 1), (1, 0)] {
+                        let own = &first.colors[color];
+                        let opposite = &first.colors[other];
+                        // A color seeing both colors of another cluster is false.
+                        if sees_any(&map, own, &second.colors[0])
+                            && sees_any(&map, own, &second.colors[1])
+                        {
+                            let mut deduction = Deduction::new("Multi-Colors", cells.clone());
+                            for &id in own {
+                                deduction.eliminate(id, value);
+                            }
+                            deductions.push(deduction);
+                        }
+                        // Pairs are taken once, with the first cluster on the lower index.
+                        if first_idx > second_idx {
+                            continue;
+                        }
+                        for (seen, free) in [(0, 1), (1, 0)] {
+                            if !sees_any(&map, own, &second.colors[seen]) {
+                                continue;
+                            }
+                            let targets =
+                                trapped(&map, opposite, &second.colors[free], &[first, second]);
+                            if targets.is_empty() {
+                                continue;
+                            }
+                            let mut deduction = Deduction::new("Multi-Colors", cells.clone());
+                            for target in targets {
+                                deduction.eliminate(target, value);
+                            }
+                            deductions.push(deduction);
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Multi-Colors"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::with_ones;
+
+    #[test]
+    fn test_color_wrap() {
+        // Chain r1c1 = r1c5 = r5c5 = r5c2 = r2c2, r1c1 and r2c2 share area 1.
+        let sudoku = with_ones(&[1, 5, 41, 38, 11, 21]);
+        let deductions = SimpleColoringStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique(), "Color Wrap");
+        assert_eq!(deductions[0].eliminations(), &[(1, 1), (11, 1), (41, 1)]);
+    }
+
+    #[test]
+    fn test_color_trap() {
+        // Chain r1c1 = r1c5 = r4c5 = r4c2, r2c2 sees r1c1 and r4c2.
+        let sudoku = with_ones(&[1, 5, 32, 29, 11, 74, 21]);
+        let deductions = SimpleColoringStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique(), "Color Trap");
+        assert_eq!(deductions[0].cells(), &[1, 32, 5, 29]);
+        assert_eq!(deductions[0].eliminations(), &[(11, 1)]);
+    }
+
+    #[test]
+    fn test_multi_colors() {
+        // Clusters r1c1 = r1c5 and r3c2 = r9c2, r1c1 sees r3c2 so r9c5 can't be 1.
+        let sudoku = with_ones(&[1, 5, 20, 74, 77, 81, 50, 12]);
+        let deductions = MultiColoringStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].cells(), &[1, 5, 20, 74]);
+        assert_eq!(deductions[0].eliminations(), &[(77, 1)]);
+    }
+}
@@ -1,44 +1,208 @@
-use crate::traits::{SimpleSudoku, Strategy};
-use tracing::{debug, info};
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::VecDeque;
+use tracing::{debug, error, info};
 
+/// Naked and hidden singles by work queues: every placed value is removed from its
+/// peers once, and only the units that lost a candidate are checked for values with a
+/// single position left. Slots solved either way are queued in turn, so the work is
+/// linear in the number of changes instead of rescanning the grid until nothing
+/// changes.
 #[derive(Debug)]
 pub struct ConstraintPropagationStrategy;
 
 impl<S: SimpleSudoku> Strategy<S> for ConstraintPropagationStrategy {
     fn run(&self, sudoku: &mut S) {
         info!("ConstraintPropagationStrategy: started.");
+        let slots = sudoku.slots();
+        let mut units_of = vec![Vec::new(); slots.len()];
+        let units: Vec<Vec<usize>> = sudoku
+            .units()
+            .iter()
+            .map(|unit| unit.iter().map(|slot| slot.borrow().id() - 1).collect())
+            .collect();
+        for (unit_idx, unit) in units.iter().enumerate() {
+            for &idx in unit {
+                units_of[idx].push(unit_idx);
+            }
+        }
+        // Placed values still to remove from the peers.
+        let mut placed: VecDeque<usize> = (0..slots.len())
+            .filter(|&idx| slots[idx].borrow().is_solved())
+            .collect();
+        // Units that lost a candidate since they were last checked for hidden singles.
+        let mut changed: VecDeque<usize> = (0..units.len()).collect();
+        let mut queued = vec![true; units.len()];
 
         loop {
-            let mut progress_made = false;
+            while let Some(idx) = placed.pop_front() {
+                let value = slots[idx].borrow().get_value().expect("Placed slot.");
+                // Peers shared by two units are visited twice, the second time is a no-op.
+                let peers = units_of[idx].iter().flat_map(|&unit| &units[unit]);
+                for &peer in peers.filter(|&&peer| peer != idx) {
+                    let mut slot = slots[peer].borrow_mut();
+                    if slot.get_value() == Some(value) {
+                        error!(
+                            "ConstraintPropagationStrategy: {:#} repeats the value of a peer.",
+                            *slot
+                        );
+                        return;
+                    }
+                    if slot.is_solved() || !slot.has_candidate(value) {
+                        continue;
+                    }
+                    slot.exclude_value(value);
+                    for &unit in &units_of[peer] {
+                        if !queued[unit] {
+                            queued[unit] = true;
+                            changed.push_back(unit);
+                        }
+                    }
+                    if slot.is_solved() {
+                        debug!("ConstraintPropagationStrategy: has solved the {:#}.", *slot);
+                        placed.push_back(peer);
+                    }
+                }
+            }
 
-            for slot_ref in sudoku.slots() {
-                if slot_ref.borrow().is_solved() {
-                    continue;
+            // Hidden singles are looked for once the placements are propagated, so the
+            // candidates of every slot are up to date.
+            let Some(unit) = changed.pop_front() else {
+                break;
+            };
+            queued[unit] = false;
+            for value in 1..=S::number_of_slots_in_area() as u8 {
+                let mut positions = units[unit].iter().filter(|&&idx| {
+                    let slot = slots[idx].borrow();
+                    slot.get_value() == Some(value)
+                        || (!slot.is_solved() && slot.has_candidate(value))
+                });
+                if let (Some(&idx), None) = (positions.next(), positions.next()) {
+                    let mut slot = slots[idx].borrow_mut();
+                    if !slot.is_solved() {
+                        slot.set_value(Some(value));
+                        debug!("ConstraintPropagationStrategy: has solved the {:#}.", *slot);
+                        placed.push_back(idx);
+                        // Its other candidates are gone from the units as well.
+                        for &other in &units_of[idx] {
+                            if !queued[other] {
+                                queued[other] = true;
+                                changed.push_back(other);
+                            }
+                        }
+                    }
                 }
+            }
+        }
 
-                // Get all solved peer values using the helper method
-                let items_to_remove = slot_ref.borrow().get_solved_peers_values();
+        debug!("ConstraintPropagationStrategy: completed.");
+    }
 
-                {
-                    let mut current_slot = slot_ref.borrow_mut();
-                    let solved_before = current_slot.is_solved();
-                    current_slot.exclude_values(items_to_remove);
+    /// Naked singles: slots with one candidate left once solved peers are removed.
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            if slot.is_solved() {
+                continue;
+            }
+            if let [value] = slot.candidates().into_iter().collect::<Vec<u8>>()[..] {
+                let mut deduction = Deduction::new("Naked Single", vec![slot.id()]);
+                deduction.place(slot.id(), value);
+                deductions.push(deduction);
+            }
+        }
+        deductions
+    }
 
-                    if solved_before != current_slot.is_solved() {
-                        progress_made = true;
-                        debug!(
-                            "ConstraintPropagationStrategy: has solved the {:#}.",
-                            current_slot
-                        );
-                    }
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Naked Single"]
+    }
+}
+
+/// The former `ConstraintPropagationStrategy`: removes the values of solved peers from
+/// every slot, rescanning the whole grid until nothing changes. Kept as the baseline
+/// the queue is tested and benchmarked against.
+#[derive(Debug)]
+pub struct RescanPropagationStrategy;
+
+impl<S: SimpleSudoku> Strategy<S> for RescanPropagationStrategy {
+    fn run(&self, sudoku: &mut S) {
+        loop {
+            let mut progress_made = false;
+            for slot in sudoku.slots() {
+                if slot.borrow().is_solved() {
+                    continue;
                 }
+                let items_to_remove = slot.borrow().get_solved_peers_values();
+                let mut slot = slot.borrow_mut();
+                slot.exclude_values(items_to_remove);
+                progress_made |= slot.is_solved();
             }
-
             if !progress_made {
                 break;
             }
         }
+    }
+}
 
-        debug!("ConstraintPropagationStrategy: completed.");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::traits::{HasSlots, Solvable};
+    use rstest::rstest;
+
+    fn values(sudoku: &Sudoku9x9) -> Vec<Option<u8>> {
+        sudoku
+            .slots()
+            .iter()
+            .map(|slot| slot.borrow().get_value())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::easy("fixtures/easy.csv", "fixtures/easy_solved.csv")]
+    #[case::average("fixtures/average.csv", "fixtures/average_solved.csv")]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    #[case::hard_2("fixtures/hard_2.csv", "fixtures/hard_2_solved.csv")]
+    fn test_queue_extends_rescan(#[case] puzzle: &str, #[case] solution: &str) {
+        let mut rescanned = Sudoku9x9::new(load_csv(puzzle));
+        RescanPropagationStrategy.run(&mut rescanned);
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        ConstraintPropagationStrategy.run(&mut sudoku);
+
+        let solution = values(&Sudoku9x9::new(load_csv(solution)));
+        for ((value, rescanned), expected) in values(&sudoku)
+            .iter()
+            .zip(values(&rescanned))
+            .zip(&solution)
+        {
+            assert!(rescanned.is_none() || value == &rescanned);
+            assert!(value.is_none() || value == expected);
+        }
+        if !puzzle.ends_with("hard_1.csv") {
+            assert!(sudoku.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_hidden_single() {
+        // The 1s of rows 2 and 3 and of columns 2 and 3 leave r1c1 the only place for
+        // 1 in the first area, while no peer rules out its other candidates.
+        let mut grid = vec![vec![None; 9]; 9];
+        grid[1][4] = Some(1);
+        grid[2][7] = Some(1);
+        grid[4][1] = Some(1);
+        grid[7][2] = Some(1);
+
+        let mut rescanned = Sudoku9x9::new(grid.clone());
+        RescanPropagationStrategy.run(&mut rescanned);
+        assert_eq!(rescanned.slots()[0].borrow().get_value(), None);
+
+        let mut sudoku = Sudoku9x9::new(grid);
+        ConstraintPropagationStrategy.run(&mut sudoku);
+        assert_eq!(sudoku.slots()[0].borrow().get_value(), Some(1));
     }
 }
@@ -0,0 +1,424 @@
+use crate::objects::budget::Budget;
+use crate::observer::SolverObserver;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::HashMap;
+use std::fmt::Display;
+use tracing::{debug, error, info, warn};
+
+/// Exact-cover matrix of a grid in Knuth's dancing links form.
+///
+/// Columns are "slot holds a value" for every slot and "value is placed once" for
+/// every unit and value; units come from `SimpleSudoku::units`, so custom regions are
+/// covered too. Rows are the (slot, value) options left by the current candidates.
+pub struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    size: Vec<usize>,
+    row_of: Vec<usize>,
+    options: Vec<(usize, u8)>,
+    total_slots: usize,
+}
+
+impl DancingLinks {
+    pub fn new<S: SimpleSudoku>(sudoku: &S) -> Self {
+        let total_slots = S::total_number_of_slots();
+        let units = sudoku.units();
+        let columns = total_slots + units.len() * S::LENGTH;
+
+        // Node 0 is the root, nodes 1..=columns are the column headers.
+        let headers = columns + 1;
+        let mut links = DancingLinks {
+            left: (0..headers)
+                .map(|idx| (idx + headers - 1) % headers)
+                .collect(),
+            right: (0..headers).map(|idx| (idx + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            size: vec![0; headers],
+            row_of: vec![0; headers],
+            options: Vec::new(),
+            total_slots,
+        };
+
+        let mut units_of: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (unit_idx, unit) in units.iter().enumerate() {
+            for slot in unit {
+                units_of
+                    .entry(slot.borrow().id() - 1)
+                    .or_default()
+                    .push(unit_idx);
+            }
+        }
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            let idx = slot.id() - 1;
+            let mut values: Vec<u8> = match slot.get_value() {
+                Some(value) => vec![value],
+                None => slot.candidates().into_iter().collect(),
+            };
+            values.sort_unstable();
+            for value in values {
+                let mut row = vec![1 + idx];
+                for unit_idx in units_of.get(&idx).into_iter().flatten() {
+                    row.push(1 + total_slots + unit_idx * S::LENGTH + value as usize - 1);
+                }
+                links.add_row(&row, (idx, value));
+            }
+        }
+        links
+    }
+
+    fn add_row(&mut self, columns: &[usize], option: (usize, u8)) {
+        let row = self.options.len();
+        self.options.push(option);
+        let first = self.left.len();
+        for (offset, &column) in columns.iter().enumerate() {
+            let node = first + offset;
+            self.left.push(if offset == 0 {
+                first + columns.len() - 1
+            } else {
+                node - 1
+            });
+            self.right.push(if offset + 1 == columns.len() {
+                first
+            } else {
+                node + 1
+            });
+            self.up.push(self.up[column]);
+            self.down.push(column);
+            self.column.push(column);
+            self.row_of.push(row);
+            let last = self.up[column];
+            self.down[last] = node;
+            self.up[column] = node;
+            self.size[column] += 1;
+        }
+    }
+
+    fn cover(&mut self, column: usize) {
+        self.right[self.left[column]] = self.right[column];
+        self.left[self.right[column]] = self.left[column];
+        let mut row = self.down[column];
+        while row != column {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut row = self.up[column];
+        while row != column {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        self.right[self.left[column]] = column;
+        self.left[self.right[column]] = column;
+    }
+
+    /// Algorithm X: returns `false` once `visit` asks to stop or the budget runs out.
+    /// Rows of columns with several rows left are guesses, `depth` counts them.
+    fn search(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        depth: usize,
+        budget: &Budget,
+        observer: &mut dyn SolverObserver,
+        visit: &mut dyn FnMut(&[usize]) -> bool,
+    ) -> bool {
+        if budget.check().is_some() {
+            return false;
+        }
+        if self.right[0] == 0 {
+            return visit(chosen);
+        }
+        // The column with the fewest rows left.
+        let mut best = self.right[0];
+        let mut column = self.right[best];
+        while column != 0 {
+            if self.size[column] < self.size[best] {
+                best = column;
+            }
+            column = self.right[column];
+        }
+        if self.size[best] == 0 {
+            return true;
+        }
+        let guess = self.size[best] > 1;
+
+        self.cover(best);
+        let mut row = self.down[best];
+        while row != best {
+            let (idx, value) = self.options[self.row_of[row]];
+            chosen.push(self.row_of[row]);
+            budget.spend(1);
+            if guess {
+                observer.guessed(idx + 1, value, depth);
+            }
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+            let proceed = self.search(chosen, depth + usize::from(guess), budget, observer, visit);
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            chosen.pop();
+            if !proceed {
+                self.uncover(best);
+                return false;
+            }
+            if guess {
+                observer.undone(idx + 1, value, depth);
+            }
+            row = self.down[row];
+        }
+        self.uncover(best);
+        true
+    }
+
+    /// Call `visit` with the values of every slot (indexed by `id - 1`) for at most
+    /// `limit` solutions.
+    pub fn for_each_solution(&mut self, limit: usize, visit: impl FnMut(&[u8])) {
+        self.for_each_solution_within(limit, &Budget::unlimited(), visit);
+    }
+
+    /// `for_each_solution`, stopping once the budget runs out. Every chosen row is a
+    /// search node.
+    pub fn for_each_solution_within(
+        &mut self,
+        limit: usize,
+        budget: &Budget,
+        visit: impl FnMut(&[u8]),
+    ) {
+        self.for_each_solution_observed(limit, budget, &mut (), visit);
+    }
+
+    /// `for_each_solution_within` reporting guesses and undos to the observer.
+    pub fn for_each_solution_observed(
+        &mut self,
+        limit: usize,
+        budget: &Budget,
+        observer: &mut dyn SolverObserver,
+        mut visit: impl FnMut(&[u8]),
+    ) {
+        if limit == 0 {
+            return;
+        }
+        let mut found = 0;
+        let options = self.options.clone();
+        let total_slots = self.total_slots;
+        self.search(
+            &mut Vec::new(),
+            0,
+            budget,
+            observer,
+            &mut |rows: &[usize]| {
+                let mut values = vec![0; total_slots];
+                for &row in rows {
+                    let (idx, value) = options[row];
+                    values[idx] = value;
+                }
+                visit(&values);
+                found += 1;
+                found < limit
+            },
+        );
+    }
+
+    /// Number of solutions, counting stops at `limit`.
+    pub fn count_solutions(&mut self, limit: usize) -> usize {
+        let mut count = 0;
+        self.for_each_solution(limit, |_| count += 1);
+        count
+    }
+
+    /// At most `limit` solutions, as values of every slot indexed by `id - 1`.
+    pub fn solutions(&mut self, limit: usize) -> Vec<Vec<u8>> {
+        self.solutions_within(limit, &Budget::unlimited())
+    }
+
+    /// `solutions`, stopping once the budget runs out.
+    pub fn solutions_within(&mut self, limit: usize, budget: &Budget) -> Vec<Vec<u8>> {
+        let mut solutions = Vec::new();
+        self.for_each_solution_within(limit, budget, |values| solutions.push(values.to_vec()));
+        solutions
+    }
+}
+
+/// Alternative to `BacktrackingStrategy` solving the exact-cover matrix with dancing links.
+#[derive(Debug)]
+pub struct DancingLinksStrategy;
+
+impl Display for DancingLinksStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DancingLinksStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for DancingLinksStrategy {
+    fn run(&self, sudoku: &mut S) {
+        self.run_within(sudoku, &Budget::unlimited());
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        self.run_observed(sudoku, budget, &mut ());
+    }
+
+    fn run_observed(&self, sudoku: &mut S, budget: &Budget, observer: &mut dyn SolverObserver) {
+        info!("{}: started.", self);
+        let mut solution = None;
+        DancingLinks::new(sudoku).for_each_solution_observed(1, budget, observer, |values| {
+            solution = Some(values.to_vec())
+        });
+        let Some(solution) = solution else {
+            match budget.check() {
+                Some(interruption) => warn!("{}: Stopped early: {:?}.", self, interruption),
+                None => error!("{}: No solution found.", self),
+            }
+            return;
+        };
+        for slot in sudoku.slots() {
+            let mut slot = slot.borrow_mut();
+            if !slot.is_solved() {
+                let value = solution[slot.id() - 1];
+                slot.set_value(Some(value));
+            }
+        }
+        debug!("{}: Successfully solved the puzzle.", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::budget::CancellationToken;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::observer::{EventLog, SolverEvent};
+    use crate::parsers::load_csv;
+    use crate::solver::Solver;
+    use crate::strategies::ConstraintPropagationStrategy;
+    use crate::traits::HasSlots;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    fn values(sudoku: &Sudoku9x9) -> Vec<Option<u8>> {
+        sudoku
+            .slots()
+            .iter()
+            .map(|slot| slot.borrow().get_value())
+            .collect()
+    }
+
+    #[rstest]
+    #[case::easy("fixtures/easy.csv", "fixtures/easy_solved.csv")]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    #[case::hard_2("fixtures/hard_2.csv", "fixtures/hard_2_solved.csv")]
+    fn test_dancing_links_as_backtracking_strategy(#[case] puzzle: &str, #[case] solution: &str) {
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let mut solver = Solver::new(&mut sudoku);
+        solver.add_strategy(Box::new(ConstraintPropagationStrategy));
+        solver.set_backtracking_strategy(Box::new(DancingLinksStrategy));
+        solver.solve();
+        let expected = Sudoku9x9::new(load_csv(solution));
+        assert_eq!(values(&sudoku), values(&expected));
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        assert_eq!(DancingLinks::new(&sudoku).count_solutions(10), 1);
+
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        assert_eq!(DancingLinks::new(&empty).count_solutions(25), 25);
+        assert_eq!(DancingLinks::new(&empty).count_solutions(0), 0);
+    }
+
+    #[test]
+    fn test_solutions_of_deadly_pattern() {
+        // r1c1 r1c9 r2c1 r2c9 hold 2 and 4 crosswise over areas 1 and 3.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        for (row, column) in [(0, 0), (0, 8), (1, 0), (1, 8)] {
+            grid[row][column] = None;
+        }
+        let sudoku = Sudoku9x9::new(grid);
+        let solutions = DancingLinks::new(&sudoku).solutions(10);
+        assert_eq!(solutions.len(), 2);
+        assert_eq!((solutions[0][0], solutions[0][8]), (2, 4));
+        assert_eq!((solutions[1][0], solutions[1][8]), (4, 2));
+    }
+
+    #[test]
+    fn test_budget_stops_search() {
+        let puzzle = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        let token = CancellationToken::new();
+        token.cancel();
+        for budget in [
+            Budget::unlimited().with_time_limit(Duration::ZERO),
+            Budget::unlimited().with_cancellation(token),
+        ] {
+            let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+            DancingLinksStrategy.run_within(&mut sudoku, &budget);
+            assert_eq!(values(&sudoku), values(&puzzle));
+        }
+
+        let budget = Budget::unlimited().with_node_limit(5);
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        assert!(
+            DancingLinks::new(&empty)
+                .solutions_within(1, &budget)
+                .is_empty()
+        );
+        assert_eq!(budget.nodes(), 6);
+    }
+
+    #[test]
+    fn test_observed_guesses() {
+        let count = |log: &EventLog, matches: fn(&SolverEvent) -> bool| {
+            log.events.iter().filter(|e| matches(e)).count()
+        };
+
+        let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_1.csv"));
+        let mut log = EventLog::default();
+        DancingLinksStrategy.run_observed(&mut sudoku, &Budget::unlimited(), &mut log);
+        let guesses = count(&log, |e| matches!(e, SolverEvent::Guessed { .. }));
+        assert!(guesses > 0);
+        assert!(count(&log, |e| matches!(e, SolverEvent::Undone { .. })) < guesses);
+        assert!(matches!(
+            log.events[0],
+            SolverEvent::Guessed { depth: 0, .. }
+        ));
+
+        // 7 is a candidate of r9c9 that leaves no solution, which only shows after
+        // guessing, so every guess is taken back.
+        let mut grid = load_csv("fixtures/hard_1.csv");
+        grid[8][8] = Some(7);
+        let mut sudoku = Sudoku9x9::new(grid);
+        let mut log = EventLog::default();
+        DancingLinksStrategy.run_observed(&mut sudoku, &Budget::unlimited(), &mut log);
+        let guesses = count(&log, |e| matches!(e, SolverEvent::Guessed { .. }));
+        assert!(guesses > 0);
+        assert_eq!(
+            count(&log, |e| matches!(e, SolverEvent::Undone { .. })),
+            guesses
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use crate::helpers::{digits, position, run_deductions};
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Cell with its position; a solved cell's mask holds its value.
+struct Cell {
+    row: usize,
+    column: usize,
+    area: usize,
+    solved: bool,
+    mask: u32,
+}
+
+/// Position of a cell as (line, cross-line) for one orientation of the pattern.
+type Coords = fn(&Cell) -> (usize, usize);
+
+fn name(cell: &Cell) -> String {
+    format!("r{}c{}", cell.row, cell.column)
+}
+
+/// Junior Exocet: two base cells of a mini-line holding 3 or 4 base digits together,
+/// and two target cells in the other areas of the band, on the two other lines.
+/// The companions (other band cells in the target cross-lines) don't hold base digits,
+/// and each base digit in the cross-lines (escape + target ones) outside the band can
+/// be covered by two lines. Then the targets hold the same digits as the base cells:
+/// non-base candidates are removed from the targets, and base digits missing from both
+/// targets are removed from the base cells.
+///
+/// Meant for extreme puzzles, so it isn't part of `Solver::with_defaults`.
+#[derive(Debug)]
+pub struct JuniorExocetStrategy;
+
+impl Display for JuniorExocetStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "JuniorExocetStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for JuniorExocetStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let mut cells: HashMap<usize, Cell> = HashMap::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            let (row, column, area) = position(&slot);
+            let mask = match slot.get_value() {
+                Some(value) => 1 << value,
+                None => slot
+                    .candidates()
+                    .iter()
+                    .fold(0u32, |mask, value| mask | (1 << value)),
+            };
+            let cell = Cell {
+                row,
+                column,
+                area,
+                solved: slot.is_solved(),
+                mask,
+            };
+            cells.insert(slot.id(), cell);
+        }
+        let mut ids: Vec<usize> = cells.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut deductions = Vec::new();
+        // Bases on a row (cross-lines are columns), then bases on a column.
+        let orientations: [Coords; 2] = [
+            |cell| (cell.row, cell.column),
+            |cell| (cell.column, cell.row),
+        ];
+        for coords in orientations {
+            let at: HashMap<(usize, usize), usize> =
+                ids.iter().map(|&id| (coords(&cells[&id]), id)).collect();
+            let line = |id: usize| coords(&cells[&id]).0;
+            let cross = |id: usize| coords(&cells[&id]).1;
+            let open: Vec<usize> = ids.iter().copied().filter(|id| !cells[id].solved).collect();
+
+            for (idx, &first) in open.iter().enumerate() {
+                for &second in &open[idx + 1..] {
+                    let area = cells[&first].area;
+                    if cells[&second].area != area || line(second) != line(first) {
+                        continue;
+                    }
+                    let base_mask = cells[&first].mask | cells[&second].mask;
+                    if !(3..=4).contains(&base_mask.count_ones()) {
+                        continue;
+                    }
+                    let in_area: Vec<usize> = ids
+                        .iter()
+                        .copied()
+                        .filter(|id| cells[id].area == area)
+                        .collect();
+                    let mut band: Vec<usize> = in_area.iter().map(|&id| line(id)).collect();
+                    band.sort_unstable();
+                    band.dedup();
+                    let mut escape: Vec<usize> = in_area
+                        .iter()
+                        .map(|&id| cross(id))
+                        .filter(|&c| c != cross(first) && c != cross(second))
+                        .collect();
+                    escape.sort_unstable();
+                    escape.dedup();
+                    let mut boxes: Vec<usize> = ids
+                        .iter()
+                        .filter(|&&id| line(id) == line(first) && cells[&id].area != area)
+                        .map(|id| cells[id].area)
+                        .collect();
+                    boxes.sort_unstable();
+                    boxes.dedup();
+                    if band.len() != 3 || escape.len() != 1 || boxes.len() != 2 {
+                        continue;
+                    }
+
+                    let targets_in = |area: usize, skip: &[usize]| -> Vec<usize> {
+                        open.iter()
+                            .copied()
+                            .filter(|&id| {
+                                cells[&id].area == area
+                                    && !skip.contains(&line(id))
+                                    && cells[&id].mask & base_mask != 0
+                            })
+                            .collect()
+                    };
+                    for first_target in targets_in(boxes[0], &[line(first)]) {
+                        let skip = [line(first), line(first_target)];
+                        for second_target in targets_in(boxes[1], &skip) {
+                            let companions = [
+                                at[&(line(second_target), cross(first_target))],
+                                at[&(line(first_target), cross(second_target))],
+                            ];
+                            if companions.iter().any(|id| cells[id].mask & base_mask != 0) {
+                                continue;
+                            }
+                            let cross_lines =
+                                [escape[0], cross(first_target), cross(second_target)];
+                            let covered = digits(base_mask).all(|value| {
+                                let mut lines: Vec<usize> = ids
+                                    .iter()
+                                    .copied()
+                                    .filter(|&id| {
+                                        cross_lines.contains(&cross(id))
+                                            && !band.contains(&line(id))
+                                            && cells[&id].mask & (1 << value) != 0
+                                    })
+                                    .map(line)
+                                    .collect();
+                                lines.sort_unstable();
+                                lines.dedup();
+                                lines.len() <= 2
+                            });
+                            if !covered {
+                                continue;
+                            }
+
+                            let targets = [first_target, second_target];
+                            let mut deduction = Deduction::new(
+                                "Junior Exocet",
+                                vec![first, second, first_target, second_target],
+                            );
+                            for target in targets {
+                                for value in digits(cells[&target].mask & !base_mask) {
+                                    deduction.eliminate(target, value);
+                                }
+                            }
+                            let in_targets = cells[&first_target].mask | cells[&second_target].mask;
+                            for value in digits(base_mask & !in_targets) {
+                                for base in [first, second] {
+                                    if cells[&base].mask & (1 << value) != 0 {
+                                        deduction.eliminate(base, value);
+                                    }
+                                }
+                            }
+                            if deduction.is_empty() {
+                                continue;
+                            }
+                            let values: Vec<String> =
+                                digits(base_mask).map(|value| value.to_string()).collect();
+                            deduction.set_explanation(format!(
+                                "base {} {} {{{}}} targets {} {}",
+                                name(&cells[&first]),
+                                name(&cells[&second]),
+                                values.join(""),
+                                name(&cells[&first_target]),
+                                name(&cells[&second_target])
+                            ));
+                            deductions.push(deduction);
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Junior Exocet"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::with_candidates;
+    use crate::objects::sudoku::Sudoku9x9;
+
+    /// Base r1c1 r1c2 {123} with targets r2c4 and r3c7. Below the band, base digits
+    /// are only in r4c3, r5c4 and r6c7, which holds the given candidates.
+    fn junior_exocet_grid(r6c7: &'static [u8]) -> Sudoku9x9 {
+        let no_base: &[u8] = &[4, 5, 6, 7, 8, 9];
+        let mut cells: Vec<(usize, &[u8])> = vec![
+            (1, &[1, 2, 3]),
+            (2, &[1, 2, 3]),
+            // Targets r2c4 and r3c7, companions r3c4 and r2c7.
+            (13, &[1, 2, 3, 4, 5]),
+            (25, &[1, 2, 5, 6]),
+            (22, no_base),
+            (16, no_base),
+        ];
+        // Base digits in columns 3, 4 and 7 below the band: 1 and 2 in rows 4 and 5.
+        for row in 4..=9 {
+            for column in [3, 4, 7] {
+                let id = (row - 1) * 9 + column;
+                let candidates: &[u8] = match (row, column) {
+                    (4, 3) | (5, 4) => &[1, 2, 4, 5, 6, 7, 8, 9],
+                    (6, 7) => r6c7,
+                    _ => no_base,
+                };
+                cells.push((id, candidates));
+            }
+        }
+        with_candidates(&cells)
+    }
+
+    #[test]
+    fn test_junior_exocet() {
+        // 3 is in row 6 only, so every base digit is covered by two rows.
+        let sudoku = junior_exocet_grid(&[3, 4, 5, 6, 7, 8, 9]);
+        let deductions = JuniorExocetStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].cells(), &[1, 2, 13, 25]);
+        assert_eq!(
+            deductions[0].explanation(),
+            Some("base r1c1 r1c2 {123} targets r2c4 r3c7")
+        );
+        assert_eq!(
+            deductions[0].eliminations(),
+            &[(13, 4), (13, 5), (25, 5), (25, 6)]
+        );
+    }
+
+    #[test]
+    fn test_junior_exocet_needs_cover() {
+        // 1 in r4c3, r5c4 and r6c7 can't be covered by two lines.
+        let sudoku = junior_exocet_grid(&[1, 3, 4, 5, 6, 7, 8, 9]);
+        assert!(JuniorExocetStrategy.deductions(&sudoku).is_empty());
+    }
+}
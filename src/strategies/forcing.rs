@@ -0,0 +1,442 @@
+use crate::helpers::{digits, position, run_deductions, run_found_deductions};
+use crate::objects::budget::Budget;
+use crate::objects::deduction::Deduction;
+use crate::objects::slot::Slot;
+use crate::traits::{HasSlots, Identifiable, SimpleSudoku, Strategy};
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::rc::Rc;
+
+/// Largest number of options (cell candidates or unit positions) tried at once.
+const MAX_OPTIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy)]
+enum Assumption {
+    Place(usize, u8),
+    Eliminate(usize, u8),
+}
+
+fn indices(slots: &[Rc<RefCell<Slot>>]) -> Vec<usize> {
+    slots.iter().map(|slot| slot.borrow().id() - 1).collect()
+}
+
+/// Snapshot of the grid as candidate masks indexed by `id - 1`, on which
+/// assumptions are propagated with singles only.
+struct Trial {
+    masks: Vec<u32>,
+    solved: Vec<bool>,
+    peers: Vec<Vec<usize>>,
+    units: Vec<(String, Vec<usize>)>,
+    names: Vec<String>,
+    length: u8,
+    max_depth: usize,
+}
+
+impl Trial {
+    fn new<S: SimpleSudoku>(sudoku: &S, max_depth: usize) -> Self {
+        let mut masks = Vec::new();
+        let mut solved = Vec::new();
+        let mut peers = Vec::new();
+        let mut names = Vec::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            let mask = match slot.get_value() {
+                Some(value) => 1 << value,
+                None => slot
+                    .candidates()
+                    .iter()
+                    .fold(0u32, |mask, value| mask | (1 << value)),
+            };
+            let (row, column, _) = position(&slot);
+            masks.push(mask);
+            solved.push(slot.is_solved());
+            peers.push(
+                slot.get_peers()
+                    .iter()
+                    .map(|peer| peer.borrow().id() - 1)
+                    .collect(),
+            );
+            names.push(format!("r{row}c{column}"));
+        }
+
+        let mut units = Vec::new();
+        for row in sudoku.rows() {
+            let row = row.borrow();
+            units.push((format!("row {}", row.id()), indices(row.slots())));
+        }
+        for column in sudoku.columns() {
+            let column = column.borrow();
+            units.push((format!("column {}", column.id()), indices(column.slots())));
+        }
+        for area in sudoku.areas() {
+            let area = area.borrow();
+            units.push((format!("area {}", area.id()), indices(area.slots())));
+        }
+
+        Trial {
+            masks,
+            solved,
+            peers,
+            units,
+            names,
+            length: S::LENGTH as u8,
+            max_depth,
+        }
+    }
+
+    fn describe(&self, assumption: Assumption) -> String {
+        match assumption {
+            Assumption::Place(idx, value) => format!("{}={value}", self.names[idx]),
+            Assumption::Eliminate(idx, value) => format!("{}<>{value}", self.names[idx]),
+        }
+    }
+
+    /// Apply the assumption and follow naked/hidden singles for at most `max_depth`
+    /// rounds. Returns the resulting masks, or the contradiction it led to.
+    fn propagate(&self, assumption: Assumption) -> Result<Vec<u32>, String> {
+        let mut masks = self.masks.clone();
+        let mut solved = self.solved.clone();
+        let mut queue = Vec::new();
+        match assumption {
+            Assumption::Place(idx, value) => queue.push((idx, value)),
+            Assumption::Eliminate(idx, value) => {
+                masks[idx] &= !(1 << value);
+                if masks[idx] == 0 {
+                    return Err(format!("{} has no candidates", self.names[idx]));
+                }
+            }
+        }
+
+        for _ in 0..=self.max_depth {
+            while let Some((idx, value)) = queue.pop() {
+                if masks[idx] & (1 << value) == 0 {
+                    return Err(format!("{} can't be {value}", self.names[idx]));
+                }
+                if solved[idx] {
+                    continue;
+                }
+                masks[idx] = 1 << value;
+                solved[idx] = true;
+                for &peer in &self.peers[idx] {
+                    masks[peer] &= !(1 << value);
+                    if masks[peer] == 0 {
+                        return Err(format!("{} has no candidates", self.names[peer]));
+                    }
+                }
+            }
+
+            for idx in 0..masks.len() {
+                if !solved[idx] && masks[idx].count_ones() == 1 {
+                    queue.push((idx, masks[idx].trailing_zeros() as u8));
+                }
+            }
+            for (name, unit) in &self.units {
+                for value in 1..=self.length {
+                    let places: Vec<usize> = unit
+                        .iter()
+                        .copied()
+                        .filter(|&idx| masks[idx] & (1 << value) != 0)
+                        .collect();
+                    match places[..] {
+                        [] => return Err(format!("{value} has no place in {name}")),
+                        [idx] if !solved[idx] => queue.push((idx, value)),
+                        _ => {}
+                    }
+                }
+            }
+            if queue.is_empty() {
+                break;
+            }
+        }
+        Ok(masks)
+    }
+
+    /// Placements and eliminations shared by all non-contradictory branches.
+    fn common(
+        &self,
+        technique: &'static str,
+        cells: Vec<usize>,
+        assumptions: &[Assumption],
+    ) -> Option<Deduction> {
+        let branches: Vec<Vec<u32>> = assumptions
+            .iter()
+            .filter_map(|&assumption| self.propagate(assumption).ok())
+            .collect();
+        if branches.is_empty() {
+            return None;
+        }
+
+        let mut deduction = Deduction::new(technique, cells);
+        for idx in (0..self.masks.len()).filter(|&idx| !self.solved[idx]) {
+            let first = branches[0][idx];
+            if first.count_ones() == 1 && branches.iter().all(|masks| masks[idx] == first) {
+                deduction.place(idx + 1, first.trailing_zeros() as u8);
+                continue;
+            }
+            for value in digits(self.masks[idx]) {
+                if branches.iter().all(|masks| masks[idx] & (1 << value) == 0) {
+                    deduction.eliminate(idx + 1, value);
+                }
+            }
+        }
+        if deduction.is_empty() {
+            return None;
+        }
+        let branches: Vec<String> = assumptions
+            .iter()
+            .map(|&assumption| self.describe(assumption))
+            .collect();
+        deduction.set_explanation(branches.join(" | "));
+        Some(deduction)
+    }
+}
+
+/// Bounded-depth forcing chains: every option of a cell (cell forcing), every position
+/// of a digit in a unit (unit forcing), or a candidate being true and false (digit
+/// forcing) is assumed in turn and followed with singles only. Whatever all the
+/// non-contradictory branches agree on is a deduction.
+#[derive(Debug)]
+pub struct ForcingChainStrategy {
+    /// Rounds of singles followed after each assumption.
+    pub max_depth: usize,
+}
+
+impl Default for ForcingChainStrategy {
+    fn default() -> Self {
+        Self { max_depth: 10 }
+    }
+}
+
+impl Display for ForcingChainStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ForcingChainStrategy")
+    }
+}
+
+impl ForcingChainStrategy {
+    /// Deductions found before the budget runs out, checked before every assumption set.
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        let trial = Trial::new(sudoku, self.max_depth);
+        let mut found: Vec<Deduction> = Vec::new();
+        let open: Vec<usize> = (0..trial.masks.len())
+            .filter(|&idx| !trial.solved[idx])
+            .collect();
+
+        'search: {
+            for &idx in &open {
+                if budget.check().is_some() {
+                    break 'search;
+                }
+                let options = trial.masks[idx].count_ones() as usize;
+                if !(2..=MAX_OPTIONS).contains(&options) {
+                    continue;
+                }
+                let assumptions: Vec<Assumption> = digits(trial.masks[idx])
+                    .map(|value| Assumption::Place(idx, value))
+                    .collect();
+                found.extend(trial.common("Cell Forcing Chain", vec![idx + 1], &assumptions));
+            }
+
+            for (_, unit) in &trial.units {
+                if budget.check().is_some() {
+                    break 'search;
+                }
+                for value in 1..=trial.length {
+                    let places: Vec<usize> = unit
+                        .iter()
+                        .copied()
+                        .filter(|&idx| !trial.solved[idx] && trial.masks[idx] & (1 << value) != 0)
+                        .collect();
+                    if !(2..=MAX_OPTIONS).contains(&places.len()) {
+                        continue;
+                    }
+                    let assumptions: Vec<Assumption> = places
+                        .iter()
+                        .map(|&idx| Assumption::Place(idx, value))
+                        .collect();
+                    let cells = places.iter().map(|idx| idx + 1).collect();
+                    found.extend(trial.common("Unit Forcing Chain", cells, &assumptions));
+                }
+            }
+
+            for &idx in &open {
+                if budget.check().is_some() {
+                    break 'search;
+                }
+                for value in digits(trial.masks[idx]) {
+                    let assumptions = [
+                        Assumption::Place(idx, value),
+                        Assumption::Eliminate(idx, value),
+                    ];
+                    found.extend(trial.common("Digit Forcing Chain", vec![idx + 1], &assumptions));
+                }
+            }
+        }
+
+        let mut deductions: Vec<Deduction> = Vec::new();
+        for deduction in found {
+            let known = deductions.iter().any(|other| {
+                other.placements() == deduction.placements()
+                    && other.eliminations() == deduction.eliminations()
+            });
+            if !known {
+                deductions.push(deduction);
+            }
+        }
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for ForcingChainStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &[
+            "Cell Forcing Chain",
+            "Unit Forcing Chain",
+            "Digit Forcing Chain",
+        ]
+    }
+}
+
+/// Nishio: a candidate whose placement leads, through singles only, to a
+/// contradiction within `max_depth` rounds is eliminated.
+#[derive(Debug)]
+pub struct NishioStrategy {
+    /// Rounds of singles followed after each assumption.
+    pub max_depth: usize,
+}
+
+impl Default for NishioStrategy {
+    fn default() -> Self {
+        Self { max_depth: 10 }
+    }
+}
+
+impl Display for NishioStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NishioStrategy")
+    }
+}
+
+impl NishioStrategy {
+    /// Deductions found before the budget runs out, checked before every cell.
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        let trial = Trial::new(sudoku, self.max_depth);
+        let mut deductions = Vec::new();
+        for idx in (0..trial.masks.len()).filter(|&idx| !trial.solved[idx]) {
+            if budget.check().is_some() {
+                break;
+            }
+            for value in digits(trial.masks[idx]) {
+                let assumption = Assumption::Place(idx, value);
+                if let Err(contradiction) = trial.propagate(assumption) {
+                    let mut deduction = Deduction::new("Nishio", vec![idx + 1]);
+                    deduction.eliminate(idx + 1, value);
+                    deduction.set_explanation(format!(
+                        "{}: {contradiction}",
+                        trial.describe(assumption)
+                    ));
+                    deductions.push(deduction);
+                }
+            }
+        }
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for NishioStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Nishio"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::{WITHOUT_ONE, with_candidates};
+
+    #[test]
+    fn test_cell_forcing_chain() {
+        // r1c1 {12}, r1c2 {13}, r2c1 {23}: both options of r1c1 fill the three cells.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (2, &[1, 3]), (10, &[2, 3])]);
+        let deductions = ForcingChainStrategy::default().deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.technique() == "Cell Forcing Chain" && d.cells() == [1])
+            .expect("Cell forcing chain is not found.");
+        assert_eq!(found.explanation(), Some("r1c1=1 | r1c1=2"));
+        assert!(found.placements().is_empty());
+        for elimination in [(3, 1), (3, 2), (3, 3), (4, 1), (19, 2), (11, 3)] {
+            assert!(found.eliminations().contains(&elimination));
+        }
+        assert!(!found.eliminations().contains(&(4, 2)));
+    }
+
+    #[test]
+    fn test_nishio() {
+        // r1c1 {12}, r1c2 {12}: placing 1 or 2 in r1c3 empties one of them.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (2, &[1, 2]), (3, &[1, 2, 3])]);
+        let deductions = NishioStrategy::default().deductions(&sudoku);
+        let eliminations: Vec<(usize, u8)> = deductions
+            .iter()
+            .flat_map(|d| d.eliminations().to_vec())
+            .collect();
+        assert!(eliminations.contains(&(3, 1)));
+        assert!(eliminations.contains(&(3, 2)));
+        assert!(!eliminations.contains(&(3, 3)));
+        assert!(deductions.iter().all(|d| d.explanation().is_some()));
+    }
+
+    #[test]
+    fn test_unit_forcing_chain() {
+        // Row 1 holds 1 only in r1c1 and r1c2: either way area 1 loses its other 1s.
+        let cells: Vec<(usize, &[u8])> = (3..=9).map(|id| (id, WITHOUT_ONE)).collect();
+        let sudoku = with_candidates(&cells);
+        let deductions = ForcingChainStrategy::default().deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.technique() == "Unit Forcing Chain" && d.cells() == [1, 2])
+            .expect("Unit forcing chain is not found.");
+        assert_eq!(found.explanation(), Some("r1c1=1 | r1c2=1"));
+        assert_eq!(
+            found.eliminations(),
+            &[(10, 1), (11, 1), (12, 1), (19, 1), (20, 1), (21, 1)]
+        );
+    }
+
+    #[test]
+    fn test_digit_forcing_chain() {
+        // r1c2 and r2c2 {12}: r1c1 = 1 empties one of them, so r1c1 can't be 1.
+        let sudoku = with_candidates(&[(2, &[1, 2]), (11, &[1, 2])]);
+        let deductions = ForcingChainStrategy::default().deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.technique() == "Digit Forcing Chain" && d.eliminations() == [(1, 1)])
+            .expect("Digit forcing chain is not found.");
+        assert_eq!(found.cells(), &[1]);
+        assert_eq!(found.explanation(), Some("r1c1=1 | r1c1<>1"));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::helpers::positions_of;
+use crate::objects::deduction::Deduction;
 use crate::objects::line::Line;
-use crate::traits::{SimpleSudoku, Strategy};
+use crate::traits::{HasSlots, Identifiable, SimpleSudoku, Strategy};
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
@@ -21,6 +23,14 @@ impl<S: SimpleSudoku> Strategy<S> for HiddenSingleInRowStrategy {
         apply_hidden_single_strategy::<S>(lines.to_vec(), self);
         debug!("{}: completed.", self);
     }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        hidden_single_deductions::<S>(sudoku.rows(), "r")
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Hidden Single"]
+    }
 }
 
 #[derive(Debug)]
@@ -39,6 +49,45 @@ impl<S: SimpleSudoku> Strategy<S> for HiddenSingleInColumnStrategy {
         apply_hidden_single_strategy::<S>(lines.to_vec(), self);
         debug!("{}: completed.", self);
     }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        hidden_single_deductions::<S>(sudoku.columns(), "c")
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Hidden Single"]
+    }
+}
+
+/// Values with a single slot left in a line, `prefix` names the line kind.
+fn hidden_single_deductions<S: SimpleSudoku>(
+    lines: &[Rc<RefCell<Line>>],
+    prefix: &str,
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for line in lines {
+        let line = line.borrow();
+        for value in 1..=S::LENGTH as u8 {
+            if line.has_value(value) {
+                continue;
+            }
+            if let [id] = positions_of(line.slots(), value)[..] {
+                // The pattern is the line: its other open slots can't hold the value.
+                let open = line
+                    .slots()
+                    .iter()
+                    .map(|slot| slot.borrow())
+                    .filter(|slot| !slot.is_solved())
+                    .map(|slot| slot.id())
+                    .collect();
+                let mut deduction = Deduction::new("Hidden Single", open);
+                deduction.place(id, value);
+                deduction.set_explanation(format!("{value} in {prefix}{}", line.id()));
+                deductions.push(deduction);
+            }
+        }
+    }
+    deductions
 }
 
 fn apply_hidden_single_strategy<S: SimpleSudoku>(
@@ -0,0 +1,221 @@
+use crate::helpers::{digits, position, run_deductions, subsets};
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Unsolved cell with its position and candidates as a bit mask.
+struct Cell {
+    row: usize,
+    column: usize,
+    area: usize,
+    mask: u32,
+}
+
+fn mask_of(cells: &HashMap<usize, Cell>, ids: &[usize]) -> u32 {
+    ids.iter().fold(0, |mask, id| mask | cells[id].mask)
+}
+
+fn names(cells: &HashMap<usize, Cell>, ids: &[usize]) -> String {
+    let names: Vec<String> = ids
+        .iter()
+        .map(|id| format!("r{}c{}", cells[id].row, cells[id].column))
+        .collect();
+    names.join(" ")
+}
+
+/// Sue de Coq: cells `C` of an `Area` ∩ `Line` intersection holding candidates `V`
+/// with `|V| >= |C| + 2`, completed by cells `D` of the line remainder and `E` of the
+/// area remainder with disjoint candidates `VD` and `VE`. When all those cells hold
+/// exactly as many digits as there are cells, `VD` is locked in the line, `VE` in the
+/// area and the rest of `V` in both.
+#[derive(Debug)]
+pub struct SueDeCoqStrategy;
+
+impl Display for SueDeCoqStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SueDeCoqStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for SueDeCoqStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let mut cells: HashMap<usize, Cell> = HashMap::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            if slot.is_solved() {
+                continue;
+            }
+            let (row, column, area) = position(&slot);
+            let mask = slot
+                .candidates()
+                .iter()
+                .fold(0u32, |mask, value| mask | (1 << value));
+            cells.insert(
+                slot.id(),
+                Cell {
+                    row,
+                    column,
+                    area,
+                    mask,
+                },
+            );
+        }
+        let mut ids: Vec<usize> = cells.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut deductions = Vec::new();
+        for area in 1..=S::number_of_areas() {
+            let on_line: [fn(&Cell) -> usize; 2] = [|cell| cell.row, |cell| cell.column];
+            for line_of in on_line {
+                let in_area: Vec<usize> = ids
+                    .iter()
+                    .copied()
+                    .filter(|id| cells[id].area == area)
+                    .collect();
+                let mut lines: Vec<usize> = in_area.iter().map(|id| line_of(&cells[id])).collect();
+                lines.sort_unstable();
+                lines.dedup();
+
+                for line in lines {
+                    let intersection: Vec<usize> = in_area
+                        .iter()
+                        .copied()
+                        .filter(|id| line_of(&cells[id]) == line)
+                        .collect();
+                    let line_rest: Vec<usize> = ids
+                        .iter()
+                        .copied()
+                        .filter(|id| line_of(&cells[id]) == line && cells[id].area != area)
+                        .collect();
+                    let area_rest: Vec<usize> = in_area
+                        .iter()
+                        .copied()
+                        .filter(|id| line_of(&cells[id]) != line)
+                        .collect();
+
+                    for core in subsets(&intersection, intersection.len()) {
+                        let core_mask = mask_of(&cells, &core);
+                        if core.len() < 2 || (core_mask.count_ones() as usize) < core.len() + 2 {
+                            continue;
+                        }
+                        // D and E hold at least one cell each, and no more than the core has extra digits.
+                        let extra = core_mask.count_ones() as usize - core.len();
+                        let line_sets = subsets(&line_rest, extra);
+                        let area_sets = subsets(&area_rest, extra);
+                        for line_set in &line_sets {
+                            let line_mask = mask_of(&cells, line_set);
+                            if line_mask & core_mask == 0 {
+                                continue;
+                            }
+                            for area_set in &area_sets {
+                                let area_mask = mask_of(&cells, area_set);
+                                if area_mask & core_mask == 0 || area_mask & line_mask != 0 {
+                                    continue;
+                                }
+                                let all = core_mask | line_mask | area_mask;
+                                let size = core.len() + line_set.len() + area_set.len();
+                                if all.count_ones() as usize != size {
+                                    continue;
+                                }
+
+                                let rest = core_mask & !line_mask & !area_mask;
+                                let mut pattern = core.clone();
+                                pattern.extend(line_set);
+                                pattern.extend(area_set);
+                                let mut deduction = Deduction::new("Sue de Coq", pattern);
+                                let scopes = [
+                                    (line_mask | rest, &line_rest, line_set),
+                                    (area_mask | rest, &area_rest, area_set),
+                                ];
+                                for (mask, remainder, used) in scopes {
+                                    for value in digits(mask) {
+                                        for &id in intersection.iter().chain(remainder.iter()) {
+                                            if !core.contains(&id)
+                                                && !used.contains(&id)
+                                                && cells[&id].mask & (1 << value) != 0
+                                            {
+                                                deduction.eliminate(id, value);
+                                            }
+                                        }
+                                    }
+                                }
+                                if deduction.is_empty() {
+                                    continue;
+                                }
+                                deduction.set_explanation(format!(
+                                    "C={} D={} E={}",
+                                    names(&cells, &core),
+                                    names(&cells, line_set),
+                                    names(&cells, area_set)
+                                ));
+                                deductions.push(deduction);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Sue de Coq"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::with_candidates;
+
+    #[test]
+    fn test_sue_de_coq() {
+        // C = r1c1 r1c2 {1234}, D = r1c5 {12}, E = r2c1 {34}.
+        let sudoku = with_candidates(&[
+            (1, &[1, 2, 3, 4]),
+            (2, &[1, 2, 3, 4]),
+            (5, &[1, 2]),
+            (10, &[3, 4]),
+        ]);
+        let deductions = SueDeCoqStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [1, 2, 5, 10])
+            .expect("Sue de Coq is not found.");
+        assert_eq!(found.explanation(), Some("C=r1c1 r1c2 D=r1c5 E=r2c1"));
+        assert_eq!(found.eliminations().len(), 24);
+        assert!(found.eliminations().contains(&(9, 1)));
+        assert!(found.eliminations().contains(&(3, 2)));
+        assert!(found.eliminations().contains(&(3, 4)));
+        assert!(found.eliminations().contains(&(21, 3)));
+        assert!(!found.eliminations().contains(&(11, 1)));
+    }
+
+    #[test]
+    fn test_sue_de_coq_in_column() {
+        // C = r1c1 r2c1 {1234}, D = r5c1 {12}, E = r1c2 {34}.
+        let sudoku = with_candidates(&[
+            (1, &[1, 2, 3, 4]),
+            (10, &[1, 2, 3, 4]),
+            (37, &[1, 2]),
+            (2, &[3, 4]),
+        ]);
+        let deductions = SueDeCoqStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [1, 10, 37, 2])
+            .expect("Sue de Coq is not found.");
+        assert_eq!(found.explanation(), Some("C=r1c1 r2c1 D=r5c1 E=r1c2"));
+        assert_eq!(found.eliminations().len(), 24);
+        assert!(found.eliminations().contains(&(73, 1)));
+        assert!(found.eliminations().contains(&(19, 2)));
+        assert!(found.eliminations().contains(&(19, 4)));
+        assert!(found.eliminations().contains(&(21, 3)));
+        assert!(!found.eliminations().contains(&(11, 1)));
+    }
+}
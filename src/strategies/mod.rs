@@ -1,7 +1,31 @@
+pub mod als;
 pub mod backtracking;
+pub mod chains;
+pub mod coloring;
 pub mod constraint_propagation;
+pub mod dlx;
+pub mod exocet;
+pub mod forcing;
 pub mod hidden_single;
+pub mod intersections;
+pub mod pattern_overlay;
+pub mod sat;
+pub mod single_digit;
+pub mod uniqueness;
+pub mod wings;
 
+pub use als::*;
 pub use backtracking::*;
+pub use chains::*;
+pub use coloring::*;
 pub use constraint_propagation::*;
+pub use dlx::*;
+pub use exocet::*;
+pub use forcing::*;
 pub use hidden_single::*;
+pub use intersections::*;
+pub use pattern_overlay::*;
+pub use sat::*;
+pub use single_digit::*;
+pub use uniqueness::*;
+pub use wings::*;
@@ -0,0 +1,224 @@
+use crate::helpers::{position, run_deductions, run_found_deductions};
+use crate::objects::budget::Budget;
+use crate::objects::deduction::Deduction;
+use crate::traits::{HasSlots, Identifiable, SimpleSudoku, Strategy};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Cells of every row that can hold the digit, as (id, column, area).
+type Rows = Vec<Vec<(usize, usize, usize)>>;
+
+/// Templates checked between two looks at the budget.
+const CHECK_INTERVAL: usize = 1024;
+
+/// Depth-first search of the templates: one cell per row, in distinct columns and
+/// areas. Counts how many templates use each cell, gives up past `limit` templates
+/// or once the budget runs out.
+struct Overlay<'a> {
+    rows: &'a Rows,
+    budget: &'a Budget,
+    limit: usize,
+    hits: HashMap<usize, usize>,
+    total: usize,
+    interrupted: bool,
+}
+
+impl<'a> Overlay<'a> {
+    fn new(rows: &'a Rows, budget: &'a Budget, limit: usize) -> Self {
+        let mut overlay = Self {
+            rows,
+            budget,
+            limit,
+            hits: HashMap::new(),
+            total: 0,
+            interrupted: false,
+        };
+        overlay.search((0, 0), &mut Vec::new());
+        overlay
+    }
+
+    fn search(&mut self, used: (u128, u128), chosen: &mut Vec<usize>) {
+        if self.total > self.limit || self.interrupted {
+            return;
+        }
+        let Some(row) = self.rows.get(chosen.len()) else {
+            self.total += 1;
+            for &id in chosen.iter() {
+                *self.hits.entry(id).or_default() += 1;
+            }
+            if self.total.is_multiple_of(CHECK_INTERVAL) {
+                self.interrupted = self.budget.check().is_some();
+            }
+            return;
+        };
+        for &(id, column, area) in row {
+            let (columns, areas) = (used.0 | 1 << column, used.1 | 1 << area);
+            if columns == used.0 || areas == used.1 {
+                continue;
+            }
+            chosen.push(id);
+            self.search((columns, areas), chosen);
+            chosen.pop();
+        }
+    }
+}
+
+/// Template method: for every digit enumerates all its placements (one per row,
+/// column and area) consistent with the candidates. Cells used by every template
+/// hold the digit, cells used by none can't.
+#[derive(Debug)]
+pub struct PatternOverlayStrategy {
+    /// Digits with more templates than this are skipped.
+    pub max_templates: usize,
+}
+
+impl Default for PatternOverlayStrategy {
+    fn default() -> Self {
+        Self {
+            max_templates: 100_000,
+        }
+    }
+}
+
+impl Display for PatternOverlayStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PatternOverlayStrategy")
+    }
+}
+
+impl PatternOverlayStrategy {
+    /// Deductions found before the budget runs out, checked while enumerating templates.
+    fn find<S: SimpleSudoku>(&self, sudoku: &S, budget: &Budget) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        for value in 1..=S::LENGTH as u8 {
+            let rows: Rows = sudoku
+                .rows()
+                .iter()
+                .map(|row| {
+                    row.borrow()
+                        .slots()
+                        .iter()
+                        .filter(|slot| {
+                            let slot = slot.borrow();
+                            match slot.get_value() {
+                                Some(solved) => solved == value,
+                                None => slot.candidates().contains(&value),
+                            }
+                        })
+                        .map(|slot| {
+                            let slot = slot.borrow();
+                            let (_, column, area) = position(&slot);
+                            (slot.id(), column, area)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let Overlay {
+                hits,
+                total,
+                interrupted,
+                ..
+            } = Overlay::new(&rows, budget, self.max_templates);
+            if interrupted {
+                break;
+            }
+            if total == 0 || total > self.max_templates {
+                continue;
+            }
+
+            let open: Vec<usize> = rows
+                .iter()
+                .flatten()
+                .map(|&(id, _, _)| id)
+                .filter(|id| !sudoku.slots()[id - 1].borrow().is_solved())
+                .collect();
+            let mut deduction = Deduction::new("Pattern Overlay", open.clone());
+            for id in open {
+                match hits.get(&id) {
+                    Some(&count) if count == total => deduction.place(id, value),
+                    None => deduction.eliminate(id, value),
+                    _ => {}
+                }
+            }
+            if deduction.is_empty() {
+                continue;
+            }
+            deduction.set_explanation(format!("{total} templates for {value}"));
+            deductions.push(deduction);
+        }
+        deductions
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for PatternOverlayStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        self.find(sudoku, &Budget::unlimited())
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        run_found_deductions(self, sudoku, |sudoku| self.find(sudoku, budget));
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Pattern Overlay"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::{WITHOUT_ONE, with_candidates};
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+
+    #[test]
+    fn test_pattern_overlay() {
+        // 1 of rows 1 and 2 is in columns 1 and 4, so in areas 1 and 2.
+        let cells: Vec<(usize, &[u8])> = [2, 3, 5, 6, 7, 8, 9, 11, 12, 14, 15, 16, 17, 18]
+            .iter()
+            .map(|&id| (id, WITHOUT_ONE))
+            .collect();
+        let sudoku = with_candidates(&cells);
+        let deductions = PatternOverlayStrategy::default().deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        let explanation = deductions[0].explanation().unwrap();
+        assert!(explanation.ends_with("templates for 1"));
+        assert_eq!(deductions[0].technique(), "Pattern Overlay");
+        assert!(deductions[0].placements().is_empty());
+        for elimination in [(19, 1), (24, 1), (28, 1), (31, 1), (73, 1)] {
+            assert!(deductions[0].eliminations().contains(&elimination));
+        }
+        assert!(!deductions[0].eliminations().contains(&(25, 1)));
+        assert!(!deductions[0].eliminations().contains(&(29, 1)));
+    }
+
+    #[test]
+    fn test_pattern_overlay_places_digit() {
+        // Only r1c1 is open, and every template of 2 uses it.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        grid[0][0] = None;
+        let sudoku = Sudoku9x9::new(grid);
+        let deductions = PatternOverlayStrategy::default().deductions(&sudoku);
+        let placements: Vec<(usize, u8)> = deductions
+            .iter()
+            .flat_map(|d| d.placements().to_vec())
+            .collect();
+        assert_eq!(placements, [(1, 2)]);
+    }
+
+    #[test]
+    fn test_pattern_overlay_skips_digits_with_many_templates() {
+        let cells: Vec<(usize, &[u8])> = [2, 3, 5, 6, 7, 8, 9, 11, 12, 14, 15, 16, 17, 18]
+            .iter()
+            .map(|&id| (id, WITHOUT_ONE))
+            .collect();
+        let sudoku = with_candidates(&cells);
+        let strategy = PatternOverlayStrategy { max_templates: 10 };
+        assert!(strategy.deductions(&sudoku).is_empty());
+    }
+}
@@ -0,0 +1,720 @@
+use crate::objects::budget::Budget;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::fmt::Display;
+use tracing::{debug, error, info, warn};
+
+/// Literal in the solver: `2 * variable + 1` when negated, variables are 0-based.
+type Lit = usize;
+
+fn to_lit(literal: i32) -> Lit {
+    let variable = literal.unsigned_abs() as usize - 1;
+    2 * variable + usize::from(literal < 0)
+}
+
+/// Conflict-driven clause learning SAT solver over DIMACS-style literals
+/// (`v` or `-v`, variables starting at 1).
+///
+/// Two watched literals, first-UIP learning with non-chronological backjumping,
+/// activity-based branching with phase saving and Luby restarts. Clauses can be
+/// added between calls to `solve`, e.g. to block a solution found before.
+pub struct CdclSolver {
+    clauses: Vec<Vec<Lit>>,
+    watches: Vec<Vec<usize>>,
+    assigns: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    trail_lim: Vec<usize>,
+    queue_head: usize,
+    activity: Vec<f64>,
+    activity_inc: f64,
+    phase: Vec<bool>,
+    unsatisfiable: bool,
+}
+
+impl CdclSolver {
+    pub fn new(num_variables: usize) -> Self {
+        Self {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * num_variables],
+            assigns: vec![None; num_variables],
+            level: vec![0; num_variables],
+            reason: vec![None; num_variables],
+            trail: Vec::new(),
+            trail_lim: Vec::new(),
+            queue_head: 0,
+            activity: vec![0.0; num_variables],
+            activity_inc: 1.0,
+            phase: vec![false; num_variables],
+            unsatisfiable: false,
+        }
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.assigns[lit / 2].map(|value| value != (lit & 1 == 1))
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let variable = lit / 2;
+        self.assigns[variable] = Some(lit & 1 == 0);
+        self.level[variable] = self.decision_level();
+        self.reason[variable] = reason;
+        self.trail.push(lit);
+    }
+
+    fn cancel_until(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let start = self.trail_lim[level];
+        for &lit in &self.trail[start..] {
+            let variable = lit / 2;
+            self.phase[variable] = lit & 1 == 0;
+            self.assigns[variable] = None;
+            self.reason[variable] = None;
+        }
+        self.trail.truncate(start);
+        self.trail_lim.truncate(level);
+        self.queue_head = self.trail.len();
+    }
+
+    /// Add a clause of DIMACS literals. Returns `false` if the formula became unsatisfiable.
+    pub fn add_clause(&mut self, literals: &[i32]) -> bool {
+        self.cancel_until(0);
+        let mut clause: Vec<Lit> = Vec::new();
+        for &literal in literals {
+            let lit = to_lit(literal);
+            if clause.contains(&(lit ^ 1)) || self.value(lit) == Some(true) {
+                // Tautology or satisfied at the top level.
+                return !self.unsatisfiable;
+            }
+            if !clause.contains(&lit) && self.value(lit).is_none() {
+                clause.push(lit);
+            }
+        }
+        match clause.len() {
+            0 => self.unsatisfiable = true,
+            1 => self.enqueue(clause[0], None),
+            _ => {
+                self.attach(clause);
+            }
+        }
+        !self.unsatisfiable
+    }
+
+    fn attach(&mut self, clause: Vec<Lit>) -> usize {
+        let idx = self.clauses.len();
+        self.watches[clause[0]].push(idx);
+        self.watches[clause[1]].push(idx);
+        self.clauses.push(clause);
+        idx
+    }
+
+    /// Unit propagation, returns the conflicting clause if any.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.queue_head < self.trail.len() {
+            let false_lit = self.trail[self.queue_head] ^ 1;
+            self.queue_head += 1;
+            let watching = std::mem::take(&mut self.watches[false_lit]);
+            let mut kept = Vec::with_capacity(watching.len());
+            let mut conflict = None;
+
+            for (position, &idx) in watching.iter().enumerate() {
+                if conflict.is_some() {
+                    kept.extend_from_slice(&watching[position..]);
+                    break;
+                }
+                if self.clauses[idx][0] == false_lit {
+                    self.clauses[idx].swap(0, 1);
+                }
+                let first = self.clauses[idx][0];
+                if self.value(first) == Some(true) {
+                    kept.push(idx);
+                    continue;
+                }
+                let replacement = (2..self.clauses[idx].len())
+                    .find(|&k| self.value(self.clauses[idx][k]) != Some(false));
+                if let Some(k) = replacement {
+                    self.clauses[idx].swap(1, k);
+                    let watch = self.clauses[idx][1];
+                    self.watches[watch].push(idx);
+                    continue;
+                }
+                kept.push(idx);
+                match self.value(first) {
+                    Some(false) => conflict = Some(idx),
+                    _ => self.enqueue(first, Some(idx)),
+                }
+            }
+            self.watches[false_lit] = kept;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    fn bump(&mut self, variable: usize) {
+        self.activity[variable] += self.activity_inc;
+        if self.activity[variable] > 1e100 {
+            for activity in self.activity.iter_mut() {
+                *activity *= 1e-100;
+            }
+            self.activity_inc *= 1e-100;
+        }
+    }
+
+    /// First-UIP conflict analysis: the learnt clause, asserting literal first, and
+    /// the level to backjump to.
+    fn analyze(&mut self, conflict: usize) -> (Vec<Lit>, usize) {
+        let mut seen = vec![false; self.assigns.len()];
+        let mut learnt = vec![0];
+        let mut pending = 0;
+        let mut clause = conflict;
+        let mut implied: Option<Lit> = None;
+        let mut idx = self.trail.len();
+
+        loop {
+            let skip = usize::from(implied.is_some());
+            for k in skip..self.clauses[clause].len() {
+                let lit = self.clauses[clause][k];
+                let variable = lit / 2;
+                if seen[variable] || self.level[variable] == 0 {
+                    continue;
+                }
+                seen[variable] = true;
+                self.bump(variable);
+                if self.level[variable] == self.decision_level() {
+                    pending += 1;
+                } else {
+                    learnt.push(lit);
+                }
+            }
+            loop {
+                idx -= 1;
+                if seen[self.trail[idx] / 2] {
+                    break;
+                }
+            }
+            let lit = self.trail[idx];
+            seen[lit / 2] = false;
+            implied = Some(lit);
+            pending -= 1;
+            if pending == 0 {
+                break;
+            }
+            clause = self.reason[lit / 2].expect("Implied literal has a reason.");
+        }
+        learnt[0] = implied.expect("Conflict has a literal on the current level.") ^ 1;
+
+        let mut backjump = 0;
+        if learnt.len() > 1 {
+            let deepest = (1..learnt.len())
+                .max_by_key(|&k| self.level[learnt[k] / 2])
+                .expect("Learnt clause has more literals.");
+            learnt.swap(1, deepest);
+            backjump = self.level[learnt[1] / 2];
+        }
+        (learnt, backjump)
+    }
+
+    fn pick_branch(&self) -> Option<Lit> {
+        (0..self.assigns.len())
+            .filter(|&variable| self.assigns[variable].is_none())
+            .max_by(|&a, &b| {
+                self.activity[a]
+                    .total_cmp(&self.activity[b])
+                    .then(b.cmp(&a))
+            })
+            .map(|variable| 2 * variable + usize::from(!self.phase[variable]))
+    }
+
+    /// Search for a model, `model[v - 1]` is the value of variable `v`.
+    pub fn solve(&mut self) -> Option<Vec<bool>> {
+        self.solve_within(&Budget::unlimited())
+    }
+
+    /// `solve`, giving up once the budget runs out. Every decision is a search node,
+    /// the budget is checked at every decision and conflict.
+    pub fn solve_within(&mut self, budget: &Budget) -> Option<Vec<bool>> {
+        if self.unsatisfiable {
+            return None;
+        }
+        self.cancel_until(0);
+        let mut conflicts = 0;
+        let mut restart = 1;
+        let mut restart_limit = 100 * luby(restart);
+
+        loop {
+            if budget.check().is_some() {
+                return None;
+            }
+            if let Some(conflict) = self.propagate() {
+                conflicts += 1;
+                if self.decision_level() == 0 {
+                    self.unsatisfiable = true;
+                    return None;
+                }
+                let (learnt, backjump) = self.analyze(conflict);
+                self.cancel_until(backjump);
+                if learnt.len() == 1 {
+                    self.enqueue(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let idx = self.attach(learnt);
+                    self.enqueue(asserting, Some(idx));
+                }
+                self.activity_inc /= 0.95;
+                continue;
+            }
+
+            if conflicts >= restart_limit {
+                conflicts = 0;
+                restart += 1;
+                restart_limit = 100 * luby(restart);
+                self.cancel_until(0);
+                continue;
+            }
+            let Some(lit) = self.pick_branch() else {
+                return Some(
+                    self.assigns
+                        .iter()
+                        .map(|value| value == &Some(true))
+                        .collect(),
+                );
+            };
+            budget.spend(1);
+            self.trail_lim.push(self.trail.len());
+            self.enqueue(lit, None);
+        }
+    }
+}
+
+/// `i`-th element (1-based) of the Luby sequence 1, 1, 2, 1, 1, 2, 4, ...
+fn luby(i: usize) -> usize {
+    let mut x = i - 1;
+    let (mut size, mut exponent) = (1, 0);
+    while size < x + 1 {
+        exponent += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != x {
+        size = (size - 1) / 2;
+        exponent -= 1;
+        x %= size;
+    }
+    1 << exponent
+}
+
+/// CNF encoding of a grid: variable `(slot_id - 1) * LENGTH + value` is "slot holds
+/// value". Every slot holds exactly one of its candidates and every unit holds each
+/// value exactly once. Variant constraints are added with `add_exactly_one`,
+/// `add_distinct`, `add_cage` or as raw clauses.
+pub struct SudokuEncoding {
+    length: usize,
+    total_slots: usize,
+    clauses: Vec<Vec<i32>>,
+}
+
+impl SudokuEncoding {
+    pub fn new<S: SimpleSudoku>(sudoku: &S) -> Self {
+        let mut encoding = Self {
+            length: S::LENGTH,
+            total_slots: S::total_number_of_slots(),
+            clauses: Vec::new(),
+        };
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            let id = slot.id();
+            let allowed: Vec<u8> = match slot.get_value() {
+                Some(value) => vec![value],
+                None => slot.candidates().into_iter().collect(),
+            };
+            encoding.add_clause(
+                allowed
+                    .iter()
+                    .map(|&value| encoding.variable(id, value))
+                    .collect(),
+            );
+            for value in 1..=S::LENGTH as u8 {
+                if !allowed.contains(&value) {
+                    encoding.add_clause(vec![-encoding.variable(id, value)]);
+                }
+            }
+            encoding.add_at_most_one(
+                (1..=S::LENGTH as u8)
+                    .map(|value| encoding.variable(id, value))
+                    .collect(),
+            );
+        }
+        for unit in sudoku.units() {
+            let ids: Vec<usize> = unit.iter().map(|slot| slot.borrow().id()).collect();
+            for value in 1..=S::LENGTH as u8 {
+                let variables: Vec<i32> =
+                    ids.iter().map(|&id| encoding.variable(id, value)).collect();
+                encoding.add_clause(variables.clone());
+                encoding.add_at_most_one(variables);
+            }
+        }
+        encoding
+    }
+
+    /// Variable of "slot `slot_id` holds `value`".
+    pub fn variable(&self, slot_id: usize, value: u8) -> i32 {
+        ((slot_id - 1) * self.length + value as usize) as i32
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.total_slots * self.length
+    }
+
+    pub fn clauses(&self) -> &[Vec<i32>] {
+        &self.clauses
+    }
+
+    /// Add a clause, e.g. an arithmetic variant constraint spelled out over `variable`.
+    pub fn add_clause(&mut self, clause: Vec<i32>) {
+        self.clauses.push(clause);
+    }
+
+    /// Pairwise encoding of "at most one of the variables is true".
+    pub fn add_at_most_one(&mut self, variables: Vec<i32>) {
+        for (idx, &first) in variables.iter().enumerate() {
+            for &second in &variables[idx + 1..] {
+                self.clauses.push(vec![-first, -second]);
+            }
+        }
+    }
+
+    /// Exactly one of the variables is true.
+    pub fn add_exactly_one(&mut self, variables: Vec<i32>) {
+        self.add_clause(variables.clone());
+        self.add_at_most_one(variables);
+    }
+
+    /// No value repeats among the slots, e.g. a diagonal or a killer cage.
+    pub fn add_distinct(&mut self, slot_ids: &[usize]) {
+        for value in 1..=self.length as u8 {
+            let variables = slot_ids
+                .iter()
+                .map(|&id| self.variable(id, value))
+                .collect();
+            self.add_at_most_one(variables);
+        }
+    }
+
+    /// Killer cage: the slots hold distinct values adding up to `sum`. Every partial
+    /// assignment that can't be completed is forbidden by a clause.
+    pub fn add_cage(&mut self, slot_ids: &[usize], sum: usize) {
+        self.add_distinct(slot_ids);
+        self.forbid_sums(slot_ids, sum, &mut Vec::new());
+    }
+
+    /// Forbid the values of `chosen` (for the first slots) followed by every value of
+    /// the next slot that can't reach `sum`; recurse into the others. Repeated values
+    /// are left to `add_distinct`.
+    fn forbid_sums(&mut self, slot_ids: &[usize], sum: usize, chosen: &mut Vec<u8>) {
+        if chosen.len() == slot_ids.len() {
+            return;
+        }
+        let left = slot_ids.len() - chosen.len() - 1;
+        let so_far: usize = chosen.iter().map(|&value| value as usize).sum();
+        for value in 1..=self.length as u8 {
+            if chosen.contains(&value) {
+                continue;
+            }
+            let total = so_far + value as usize;
+            let reachable = total + left * (left + 1) / 2 <= sum
+                && total + (0..left).map(|k| self.length - k).sum::<usize>() >= sum;
+            if reachable {
+                chosen.push(value);
+                self.forbid_sums(slot_ids, sum, chosen);
+                chosen.pop();
+                continue;
+            }
+            let clause = slot_ids
+                .iter()
+                .zip(chosen.iter().chain([&value]))
+                .map(|(&id, &value)| -self.variable(id, value))
+                .collect();
+            self.add_clause(clause);
+        }
+    }
+
+    pub fn solver(&self) -> CdclSolver {
+        let mut solver = CdclSolver::new(self.num_variables());
+        for clause in &self.clauses {
+            solver.add_clause(clause);
+        }
+        solver
+    }
+
+    /// Values of every slot (indexed by `id - 1`) in a model.
+    pub fn decode(&self, model: &[bool]) -> Vec<u8> {
+        (1..=self.total_slots)
+            .map(|id| {
+                (1..=self.length as u8)
+                    .find(|&value| model[self.variable(id, value) as usize - 1])
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    /// Clause excluding the given solution, to look for another one.
+    pub fn blocking_clause(&self, values: &[u8]) -> Vec<i32> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(idx, &value)| -self.variable(idx + 1, value))
+            .collect()
+    }
+
+    /// At most `limit` solutions, each found solution blocked before the next search.
+    pub fn solutions(&self, limit: usize) -> Vec<Vec<u8>> {
+        self.solutions_within(limit, &Budget::unlimited())
+    }
+
+    /// `solutions`, stopping once the budget runs out.
+    pub fn solutions_within(&self, limit: usize, budget: &Budget) -> Vec<Vec<u8>> {
+        let mut solver = self.solver();
+        let mut solutions = Vec::new();
+        while solutions.len() < limit {
+            let Some(model) = solver.solve_within(budget) else {
+                break;
+            };
+            let values = self.decode(&model);
+            solver.add_clause(&self.blocking_clause(&values));
+            solutions.push(values);
+        }
+        solutions
+    }
+
+    /// Solution exists and no second one can be found.
+    pub fn has_unique_solution(&self) -> bool {
+        self.solutions(2).len() == 1
+    }
+}
+
+/// Killer cage: slots (by id) holding distinct values that add up to `sum`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cage {
+    pub slot_ids: Vec<usize>,
+    pub sum: usize,
+}
+
+/// Alternative to `BacktrackingStrategy` solving the CNF encoding of the grid with the
+/// embedded CDCL solver. Killer cages and `extra_clauses`, over the
+/// `SudokuEncoding::variable` numbering, add variant constraints.
+#[derive(Debug, Default)]
+pub struct SatStrategy {
+    pub cages: Vec<Cage>,
+    pub extra_clauses: Vec<Vec<i32>>,
+}
+
+impl SatStrategy {
+    pub fn with_cage(mut self, slot_ids: Vec<usize>, sum: usize) -> Self {
+        self.cages.push(Cage { slot_ids, sum });
+        self
+    }
+}
+
+impl Display for SatStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SatStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for SatStrategy {
+    fn run(&self, sudoku: &mut S) {
+        self.run_within(sudoku, &Budget::unlimited());
+    }
+
+    fn run_within(&self, sudoku: &mut S, budget: &Budget) {
+        info!("{}: started.", self);
+        let mut encoding = SudokuEncoding::new(sudoku);
+        for cage in &self.cages {
+            encoding.add_cage(&cage.slot_ids, cage.sum);
+        }
+        for clause in &self.extra_clauses {
+            encoding.add_clause(clause.clone());
+        }
+        let Some(solution) = encoding.solutions_within(1, budget).pop() else {
+            match budget.check() {
+                Some(interruption) => warn!("{}: Stopped early: {:?}.", self, interruption),
+                None => error!("{}: No solution found.", self),
+            }
+            return;
+        };
+        for slot in sudoku.slots() {
+            let mut slot = slot.borrow_mut();
+            if !slot.is_solved() {
+                let value = solution[slot.id() - 1];
+                slot.set_value(Some(value));
+            }
+        }
+        debug!("{}: Successfully solved the puzzle.", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::budget::CancellationToken;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::solver::Solver;
+    use crate::strategies::ConstraintPropagationStrategy;
+    use crate::traits::HasSlots;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    fn values(sudoku: &Sudoku9x9) -> Vec<Option<u8>> {
+        sudoku
+            .slots()
+            .iter()
+            .map(|slot| slot.borrow().get_value())
+            .collect()
+    }
+
+    #[test]
+    fn test_cdcl_solver() {
+        let mut solver = CdclSolver::new(3);
+        solver.add_clause(&[1, 2]);
+        solver.add_clause(&[-1, 3]);
+        solver.add_clause(&[-2, 3]);
+        let model = solver.solve().expect("Formula is satisfiable.");
+        assert!(model[2]);
+        assert!(model[0] || model[1]);
+
+        solver.add_clause(&[-3]);
+        assert_eq!(solver.solve(), None);
+    }
+
+    #[test]
+    fn test_cdcl_solver_pigeonhole() {
+        // Three pigeons in two holes, variable 2 * pigeon + hole + 1.
+        let mut solver = CdclSolver::new(6);
+        for pigeon in 0..3 {
+            solver.add_clause(&[2 * pigeon + 1, 2 * pigeon + 2]);
+        }
+        for hole in 1..=2 {
+            for first in 0..3 {
+                for second in first + 1..3 {
+                    solver.add_clause(&[-(2 * first + hole), -(2 * second + hole)]);
+                }
+            }
+        }
+        assert_eq!(solver.solve(), None);
+    }
+
+    #[test]
+    fn test_luby() {
+        let sequence: Vec<usize> = (1..=15).map(luby).collect();
+        assert_eq!(sequence, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[rstest]
+    #[case::easy("fixtures/easy.csv", "fixtures/easy_solved.csv")]
+    #[case::hard_1("fixtures/hard_1.csv", "fixtures/hard_1_solved.csv")]
+    #[case::hard_2("fixtures/hard_2.csv", "fixtures/hard_2_solved.csv")]
+    fn test_sat_as_backtracking_strategy(#[case] puzzle: &str, #[case] solution: &str) {
+        let mut sudoku = Sudoku9x9::new(load_csv(puzzle));
+        let mut solver = Solver::new(&mut sudoku);
+        solver.add_strategy(Box::new(ConstraintPropagationStrategy));
+        solver.set_backtracking_strategy(Box::new(SatStrategy::default()));
+        solver.solve();
+        let expected = Sudoku9x9::new(load_csv(solution));
+        assert_eq!(values(&sudoku), values(&expected));
+    }
+
+    #[test]
+    fn test_uniqueness_check() {
+        let sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        assert!(SudokuEncoding::new(&sudoku).has_unique_solution());
+
+        // r1c1 r1c9 r2c1 r2c9 hold 2 and 4 crosswise over areas 1 and 3.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        for (row, column) in [(0, 0), (0, 8), (1, 0), (1, 8)] {
+            grid[row][column] = None;
+        }
+        let sudoku = Sudoku9x9::new(grid);
+        let mut encoding = SudokuEncoding::new(&sudoku);
+        assert_eq!(encoding.solutions(10).len(), 2);
+
+        // A variant constraint picks one of them.
+        encoding.add_clause(vec![encoding.variable(1, 4)]);
+        let solutions = encoding.solutions(10);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0][0], 4);
+    }
+
+    #[test]
+    fn test_budget_stops_search() {
+        let puzzle = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+        let token = CancellationToken::new();
+        token.cancel();
+        for budget in [
+            Budget::unlimited().with_time_limit(Duration::ZERO),
+            Budget::unlimited().with_cancellation(token),
+        ] {
+            let mut sudoku = Sudoku9x9::new(load_csv("fixtures/hard_2.csv"));
+            SatStrategy::default().run_within(&mut sudoku, &budget);
+            assert_eq!(values(&sudoku), values(&puzzle));
+        }
+
+        // An interrupted search isn't mistaken for an unsatisfiable formula.
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        let encoding = SudokuEncoding::new(&empty);
+        let mut solver = encoding.solver();
+        let budget = Budget::unlimited().with_node_limit(5);
+        assert_eq!(solver.solve_within(&budget), None);
+        assert_eq!(budget.nodes(), 6);
+        assert!(solver.solve().is_some());
+    }
+
+    #[test]
+    fn test_cage_constraint() {
+        // Two cells of a cage summing to 3 hold 1 and 2.
+        let empty = Sudoku9x9::new(vec![vec![None; 9]; 9]);
+        let mut encoding = SudokuEncoding::new(&empty);
+        encoding.add_cage(&[1, 2], 3);
+        for solution in encoding.solutions(5) {
+            let mut cage = [solution[0], solution[1]];
+            cage.sort_unstable();
+            assert_eq!(cage, [1, 2]);
+        }
+        encoding.add_exactly_one(vec![encoding.variable(1, 2), encoding.variable(3, 2)]);
+        assert!(
+            encoding
+                .solutions(5)
+                .iter()
+                .all(|solution| solution[0] == 2)
+        );
+
+        // r1c1 r1c9 r2c1 r2c9 hold 2 and 4 crosswise over areas 1 and 3, r1c2 is 7.
+        let mut grid = load_csv("fixtures/hard_1_solved.csv");
+        for (row, column) in [(0, 0), (0, 8), (1, 0), (1, 8)] {
+            grid[row][column] = None;
+        }
+        let sudoku = Sudoku9x9::new(grid.clone());
+        let mut encoding = SudokuEncoding::new(&sudoku);
+        assert!(!encoding.has_unique_solution());
+        encoding.add_cage(&[1, 2], 11);
+        let mut solver = encoding.solver();
+        let solution = encoding.decode(&solver.solve().expect("The cage fits a solution."));
+        assert_eq!((solution[0], solution[8]), (4, 2));
+        solver.add_clause(&encoding.blocking_clause(&solution));
+        assert_eq!(solver.solve(), None);
+        assert!(encoding.has_unique_solution());
+
+        let mut sudoku = Sudoku9x9::new(grid);
+        SatStrategy::default()
+            .with_cage(vec![1, 2], 11)
+            .run(&mut sudoku);
+        assert_eq!(sudoku.slots()[0].borrow().get_value(), Some(4));
+    }
+}
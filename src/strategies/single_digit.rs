@@ -0,0 +1,439 @@
+use crate::helpers::{peer_ids, position, run_deductions};
+use crate::objects::deduction::Deduction;
+use crate::objects::slot::Slot;
+use crate::traits::{HasSlots, Identifiable, SimpleSudoku, Strategy};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum UnitKind {
+    Row,
+    Column,
+    Area,
+}
+
+/// Conjugate pair: the only two places for a digit in a unit (or several units).
+pub(crate) struct StrongLink {
+    pub(crate) ends: (usize, usize),
+    pub(crate) kinds: Vec<UnitKind>,
+}
+
+/// Unsolved slot that can still hold the digit.
+pub(crate) struct DigitCell {
+    pub(crate) row: usize,
+    pub(crate) column: usize,
+    pub(crate) area: usize,
+    pub(crate) peers: HashSet<usize>,
+}
+
+/// Positions and conjugate pairs of a single digit.
+pub(crate) struct DigitMap {
+    pub(crate) value: u8,
+    pub(crate) cells: HashMap<usize, DigitCell>,
+    pub(crate) links: Vec<StrongLink>,
+}
+
+impl DigitMap {
+    pub(crate) fn new<S: SimpleSudoku>(sudoku: &S, value: u8) -> Self {
+        let cells: HashMap<usize, DigitCell> = sudoku
+            .slots()
+            .iter()
+            .filter(|slot| {
+                let slot = slot.borrow();
+                !slot.is_solved() && slot.candidates().contains(&value)
+            })
+            .map(|slot| {
+                let slot = slot.borrow();
+                let (row, column, area) = position(&slot);
+                let cell = DigitCell {
+                    row,
+                    column,
+                    area,
+                    peers: peer_ids(&slot),
+                };
+                (slot.id(), cell)
+            })
+            .collect();
+
+        // Positions of the digit in a unit, by the candidates left once solved peers
+        // are removed.
+        let holding = |slots: &[Rc<RefCell<Slot>>]| -> Vec<usize> {
+            slots
+                .iter()
+                .map(|slot| slot.borrow().id())
+                .filter(|id| cells.contains_key(id))
+                .collect()
+        };
+        let mut links: Vec<StrongLink> = Vec::new();
+        let mut add_link = |ids: Vec<usize>, kind: UnitKind| {
+            let [first, second] = ids[..] else {
+                return;
+            };
+            let ends = (first.min(second), first.max(second));
+            match links.iter_mut().find(|link| link.ends == ends) {
+                Some(link) => link.kinds.push(kind),
+                None => links.push(StrongLink {
+                    ends,
+                    kinds: vec![kind],
+                }),
+            }
+        };
+        for row in sudoku.rows() {
+            add_link(holding(row.borrow().slots()), UnitKind::Row);
+        }
+        for column in sudoku.columns() {
+            add_link(holding(column.borrow().slots()), UnitKind::Column);
+        }
+        for area in sudoku.areas() {
+            add_link(holding(area.borrow().slots()), UnitKind::Area);
+        }
+
+        DigitMap {
+            value,
+            cells,
+            links,
+        }
+    }
+
+    /// Cells holding the digit that see every one of `seen_by`, except `excluded`.
+    pub(crate) fn seen_by_all(&self, seen_by: &[usize], excluded: &[usize]) -> Vec<usize> {
+        let mut targets: Vec<usize> = self
+            .cells
+            .iter()
+            .filter(|(id, cell)| {
+                !excluded.contains(id) && seen_by.iter().all(|other| cell.peers.contains(other))
+            })
+            .map(|(&id, _)| id)
+            .collect();
+        targets.sort_unstable();
+        targets
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum TwoLinkPattern {
+    Skyscraper,
+    TwoStringKite,
+    TurbotFish,
+}
+
+/// Two strong links joined by a weak link: `a = b - c = d`.
+struct TwoLinkChain {
+    pattern: TwoLinkPattern,
+    chain: [usize; 4],
+    eliminations: Vec<usize>,
+}
+
+fn classify(
+    map: &DigitMap,
+    first: &StrongLink,
+    second: &StrongLink,
+    b: usize,
+    c: usize,
+) -> TwoLinkPattern {
+    let (cell_b, cell_c) = (&map.cells[&b], &map.cells[&c]);
+    for first_kind in &first.kinds {
+        for second_kind in &second.kinds {
+            match (first_kind, second_kind) {
+                (UnitKind::Row, UnitKind::Row) if cell_b.column == cell_c.column => {
+                    return TwoLinkPattern::Skyscraper;
+                }
+                (UnitKind::Column, UnitKind::Column) if cell_b.row == cell_c.row => {
+                    return TwoLinkPattern::Skyscraper;
+                }
+                (UnitKind::Row, UnitKind::Column) | (UnitKind::Column, UnitKind::Row)
+                    if cell_b.area == cell_c.area =>
+                {
+                    return TwoLinkPattern::TwoStringKite;
+                }
+                _ => {}
+            }
+        }
+    }
+    TwoLinkPattern::TurbotFish
+}
+
+fn two_link_chains(map: &DigitMap) -> Vec<TwoLinkChain> {
+    let mut chains = Vec::new();
+    for (idx, first) in map.links.iter().enumerate() {
+        for second in &map.links[idx + 1..] {
+            let first_ends = [first.ends, (first.ends.1, first.ends.0)];
+            let second_ends = [second.ends, (second.ends.1, second.ends.0)];
+            for (a, b) in first_ends {
+                for (c, d) in second_ends {
+                    let chain = [a, b, c, d];
+                    let distinct: HashSet<usize> = chain.iter().copied().collect();
+                    if distinct.len() != 4 || !map.cells[&b].peers.contains(&c) {
+                        continue;
+                    }
+                    let eliminations = map.seen_by_all(&[a, d], &chain);
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    chains.push(TwoLinkChain {
+                        pattern: classify(map, first, second, b, c),
+                        chain,
+                        eliminations,
+                    });
+                }
+            }
+        }
+    }
+    chains
+}
+
+fn two_link_deductions<S: SimpleSudoku>(
+    sudoku: &S,
+    pattern: TwoLinkPattern,
+    technique: &'static str,
+) -> Vec<Deduction> {
+    let mut deductions = Vec::new();
+    for value in 1..=S::LENGTH as u8 {
+        let map = DigitMap::new(sudoku, value);
+        for found in two_link_chains(&map) {
+            if found.pattern != pattern {
+                continue;
+            }
+            let mut deduction = Deduction::new(technique, found.chain.to_vec());
+            for target in found.eliminations {
+                deduction.eliminate(target, map.value);
+            }
+            deductions.push(deduction);
+        }
+    }
+    deductions
+}
+
+/// Two conjugate pairs in parallel lines whose ends meet in a crossing line.
+/// Reports the chain `a = b - c = d`; the digit is removed from cells seeing `a` and `d`.
+#[derive(Debug)]
+pub struct SkyscraperStrategy;
+
+impl Display for SkyscraperStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SkyscraperStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for SkyscraperStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        two_link_deductions(sudoku, TwoLinkPattern::Skyscraper, "Skyscraper")
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Skyscraper"]
+    }
+}
+
+/// A row and a column conjugate pair with one end of each in the same area.
+/// Reports the chain `a = b - c = d`; the digit is removed from cells seeing `a` and `d`.
+#[derive(Debug)]
+pub struct TwoStringKiteStrategy;
+
+impl Display for TwoStringKiteStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TwoStringKiteStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for TwoStringKiteStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        two_link_deductions(sudoku, TwoLinkPattern::TwoStringKite, "2-String Kite")
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["2-String Kite"]
+    }
+}
+
+/// Any other two conjugate pairs (in rows, columns or areas) joined by a weak link.
+/// Skyscrapers and 2-String Kites are reported by their own strategies.
+#[derive(Debug)]
+pub struct TurbotFishStrategy;
+
+impl Display for TurbotFishStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TurbotFishStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for TurbotFishStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        two_link_deductions(sudoku, TwoLinkPattern::TurbotFish, "Turbot Fish")
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Turbot Fish"]
+    }
+}
+
+/// An area whose candidates for a digit lie on one row and one column of it,
+/// combined with a conjugate pair in a line: the digit is removed from the cell
+/// where the far end of the pair meets the other arm of the area.
+/// Reports the area cells followed by the conjugate pair.
+#[derive(Debug)]
+pub struct EmptyRectangleStrategy;
+
+impl Display for EmptyRectangleStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EmptyRectangleStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for EmptyRectangleStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        let slot_at = |row: usize, column: usize| {
+            sudoku.rows()[row - 1].borrow().slots()[column - 1]
+                .borrow()
+                .id()
+        };
+
+        for value in 1..=S::LENGTH as u8 {
+            let map = DigitMap::new(sudoku, value);
+            for area in sudoku.areas() {
+                let area = area.borrow();
+                let area_cells: Vec<usize> = area
+                    .slots()
+                    .iter()
+                    .map(|slot| slot.borrow().id())
+                    .filter(|id| map.cells.contains_key(id))
+                    .collect();
+                if area_cells.len() < 2 {
+                    continue;
+                }
+                let rows: HashSet<usize> = area_cells.iter().map(|id| map.cells[id].row).collect();
+                let columns: HashSet<usize> =
+                    area_cells.iter().map(|id| map.cells[id].column).collect();
+
+                for &row in &rows {
+                    for &column in &columns {
+                        let on_cross = area_cells
+                            .iter()
+                            .all(|id| map.cells[id].row == row || map.cells[id].column == column);
+                        let row_arm = area_cells
+                            .iter()
+                            .any(|id| map.cells[id].row == row && map.cells[id].column != column);
+                        let column_arm = area_cells
+                            .iter()
+                            .any(|id| map.cells[id].column == column && map.cells[id].row != row);
+                        if !on_cross || !row_arm || !column_arm {
+                            continue;
+                        }
+
+                        for link in &map.links {
+                            let (p, q) = link.ends;
+                            if area_cells.contains(&p) || area_cells.contains(&q) {
+                                continue;
+                            }
+                            for (near, far) in [(p, q), (q, p)] {
+                                let (near_cell, far_cell) = (&map.cells[&near], &map.cells[&far]);
+                                let target = if link.kinds.contains(&UnitKind::Column)
+                                    && near_cell.row == row
+                                {
+                                    slot_at(far_cell.row, column)
+                                } else if link.kinds.contains(&UnitKind::Row)
+                                    && near_cell.column == column
+                                {
+                                    slot_at(row, far_cell.column)
+                                } else {
+                                    continue;
+                                };
+                                let Some(target_cell) = map.cells.get(&target) else {
+                                    continue;
+                                };
+                                if target_cell.area == area.id() {
+                                    continue;
+                                }
+                                let mut cells = area_cells.clone();
+                                cells.extend([near, far]);
+                                let mut deduction = Deduction::new("Empty Rectangle", cells);
+                                deduction.eliminate(target, value);
+                                deductions.push(deduction);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Empty Rectangle"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::with_ones;
+
+    #[test]
+    fn test_skyscraper() {
+        // Rows 1 and 4 hold 1 only in c1/c5 and c1/c6.
+        let sudoku = with_ones(&[1, 5, 28, 33, 15, 41, 70, 81]);
+        let deductions = SkyscraperStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [5, 1, 28, 33])
+            .expect("Skyscraper is not found.");
+        assert_eq!(found.eliminations(), &[(15, 1), (41, 1)]);
+    }
+
+    #[test]
+    fn test_empty_rectangle() {
+        // Area 1 holds 1 on r1 and c1, column 5 holds 1 only in r1 and r6.
+        let sudoku = with_ones(&[2, 3, 10, 19, 5, 50, 46]);
+        let deductions = EmptyRectangleStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells().ends_with(&[5, 50]))
+            .expect("Empty rectangle is not found.");
+        assert_eq!(found.eliminations(), &[(46, 1)]);
+    }
+
+    #[test]
+    fn test_two_string_kite() {
+        // Row 1 holds 1 only in c2/c7, column 1 only in r2/r7, r1c2 and r2c1 share area 1.
+        let sudoku = with_ones(&[2, 7, 10, 55, 61]);
+        let deductions = TwoStringKiteStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [7, 2, 10, 55])
+            .expect("2-String Kite is not found.");
+        assert_eq!(found.technique(), "2-String Kite");
+        assert_eq!(found.eliminations(), &[(61, 1)]);
+    }
+
+    #[test]
+    fn test_turbot_fish() {
+        // Row 6 holds 1 only in c3/c8, area 1 only in r1c1/r3c3, r6c3 sees r3c3.
+        let sudoku = with_ones(&[1, 21, 48, 53, 8, 9]);
+        let deductions = TurbotFishStrategy.deductions(&sudoku);
+        let found = deductions
+            .iter()
+            .find(|d| d.cells() == [53, 48, 21, 1])
+            .expect("Turbot Fish is not found.");
+        assert_eq!(found.technique(), "Turbot Fish");
+        assert_eq!(found.eliminations(), &[(8, 1)]);
+    }
+}
@@ -0,0 +1,678 @@
+use crate::helpers::{peer_ids, position, run_deductions, subsets};
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+
+/// Slot snapshot with everything the uniqueness patterns look at.
+struct GridCell {
+    row: usize,
+    column: usize,
+    area: usize,
+    value: Option<u8>,
+    given: bool,
+    candidates: Vec<u8>,
+    peers: HashSet<usize>,
+}
+
+struct Grid {
+    cells: HashMap<usize, GridCell>,
+    /// Slot ids by (row, column), both starting from 1.
+    layout: HashMap<(usize, usize), usize>,
+    units: Vec<Vec<usize>>,
+    rows: usize,
+    columns: usize,
+}
+
+impl Grid {
+    fn new<S: SimpleSudoku>(sudoku: &S) -> Self {
+        let mut cells = HashMap::new();
+        let mut layout = HashMap::new();
+        for slot in sudoku.slots() {
+            let slot = slot.borrow();
+            let (row, column, area) = position(&slot);
+            let mut candidates: Vec<u8> = if slot.is_solved() {
+                Vec::new()
+            } else {
+                slot.candidates().into_iter().collect()
+            };
+            candidates.sort_unstable();
+            layout.insert((row, column), slot.id());
+            cells.insert(
+                slot.id(),
+                GridCell {
+                    row,
+                    column,
+                    area,
+                    value: slot.get_value(),
+                    given: slot.is_given(),
+                    candidates,
+                    peers: peer_ids(&slot),
+                },
+            );
+        }
+        let units = sudoku
+            .units()
+            .iter()
+            .map(|unit| unit.iter().map(|slot| slot.borrow().id()).collect())
+            .collect();
+        Grid {
+            cells,
+            layout,
+            units,
+            rows: S::NUMBER_OF_ROWS,
+            columns: S::NUMBER_OF_COLS,
+        }
+    }
+
+    /// Rectangles spanning two rows, two columns and exactly two areas, as
+    /// `[top left, top right, bottom left, bottom right]`.
+    fn rectangles(&self) -> Vec<[usize; 4]> {
+        let mut rectangles = Vec::new();
+        for top in 1..=self.rows {
+            for bottom in top + 1..=self.rows {
+                for left in 1..=self.columns {
+                    for right in left + 1..=self.columns {
+                        let corners = [
+                            self.layout[&(top, left)],
+                            self.layout[&(top, right)],
+                            self.layout[&(bottom, left)],
+                            self.layout[&(bottom, right)],
+                        ];
+                        let areas: HashSet<usize> =
+                            corners.iter().map(|id| self.cells[id].area).collect();
+                        if areas.len() == 2 {
+                            rectangles.push(corners);
+                        }
+                    }
+                }
+            }
+        }
+        rectangles
+    }
+
+    fn has(&self, id: usize, value: u8) -> bool {
+        self.cells[&id].candidates.contains(&value)
+    }
+
+    /// Unsolved cells with `value` seeing every one of `seen_by`.
+    fn seen_by_all(&self, seen_by: &[usize], value: u8) -> Vec<usize> {
+        let mut targets: Vec<usize> = self
+            .cells
+            .iter()
+            .filter(|(id, cell)| {
+                !seen_by.contains(id)
+                    && cell.candidates.contains(&value)
+                    && seen_by.iter().all(|other| cell.peers.contains(other))
+            })
+            .map(|(&id, _)| id)
+            .collect();
+        targets.sort_unstable();
+        targets
+    }
+
+    /// Units containing all the cells.
+    fn common_units(&self, ids: &[usize]) -> Vec<&Vec<usize>> {
+        self.units
+            .iter()
+            .filter(|unit| ids.iter().all(|id| unit.contains(id)))
+            .collect()
+    }
+
+    fn positions_in(&self, unit: &[usize], value: u8) -> Vec<usize> {
+        unit.iter()
+            .copied()
+            .filter(|&id| self.has(id, value))
+            .collect()
+    }
+
+    fn share_line(&self, first: usize, second: usize) -> bool {
+        let (first, second) = (&self.cells[&first], &self.cells[&second]);
+        first.row == second.row || first.column == second.column
+    }
+}
+
+/// Diagonally opposite corner of a rectangle given as `[tl, tr, bl, br]`.
+fn opposite(idx: usize) -> usize {
+    3 - idx
+}
+
+/// Unique Rectangles, types 1 to 6: four unsolved cells on two rows, columns and
+/// areas all holding `{a, b}` would allow two solutions, so at least one of the
+/// extra candidates must be true.
+#[derive(Debug)]
+pub struct UniqueRectangleStrategy;
+
+impl Display for UniqueRectangleStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UniqueRectangleStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for UniqueRectangleStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let grid = Grid::new(sudoku);
+        let mut deductions = Vec::new();
+
+        for corners in grid.rectangles() {
+            if corners.iter().any(|id| grid.cells[id].value.is_some()) {
+                continue;
+            }
+            let Some(first) = corners.first() else {
+                continue;
+            };
+            let pairs: Vec<(u8, u8)> = {
+                let values = &grid.cells[first].candidates;
+                let mut pairs = Vec::new();
+                for (idx, &a) in values.iter().enumerate() {
+                    for &b in &values[idx + 1..] {
+                        if corners.iter().all(|&id| grid.has(id, a) && grid.has(id, b)) {
+                            pairs.push((a, b));
+                        }
+                    }
+                }
+                pairs
+            };
+
+            for (a, b) in pairs {
+                let floors: Vec<usize> = (0..4)
+                    .filter(|&idx| grid.cells[&corners[idx]].candidates.len() == 2)
+                    .collect();
+                let roofs: Vec<usize> = (0..4).filter(|idx| !floors.contains(idx)).collect();
+                let roof_ids: Vec<usize> = roofs.iter().map(|&idx| corners[idx]).collect();
+                let cells = corners.to_vec();
+
+                let extras: BTreeSet<u8> = roof_ids
+                    .iter()
+                    .flat_map(|id| grid.cells[id].candidates.iter().copied())
+                    .filter(|&value| value != a && value != b)
+                    .collect();
+
+                match roofs.len() {
+                    1 => {
+                        // Type 1: the only cell with extras can't be `a` or `b`.
+                        let mut deduction = Deduction::new("Unique Rectangle Type 1", cells);
+                        deduction.eliminate(roof_ids[0], a);
+                        deduction.eliminate(roof_ids[0], b);
+                        deductions.push(deduction);
+                        continue;
+                    }
+                    2 | 3 => {}
+                    _ => continue,
+                }
+
+                // Types 2 and 5: every roof has the same single extra candidate.
+                let single_extra = extras.len() == 1
+                    && roof_ids
+                        .iter()
+                        .all(|id| grid.cells[id].candidates.len() == 3);
+                if single_extra {
+                    let extra = *extras.iter().next().unwrap();
+                    let targets = grid.seen_by_all(&roof_ids, extra);
+                    if !targets.is_empty() {
+                        let technique =
+                            if roof_ids.len() == 2 && grid.share_line(roof_ids[0], roof_ids[1]) {
+                                "Unique Rectangle Type 2"
+                            } else {
+                                "Unique Rectangle Type 5"
+                            };
+                        let mut deduction = Deduction::new(technique, cells.clone());
+                        for target in targets {
+                            deduction.eliminate(target, extra);
+                        }
+                        deductions.push(deduction);
+                    }
+                }
+                if roofs.len() != 2 {
+                    continue;
+                }
+
+                if grid.share_line(roof_ids[0], roof_ids[1]) {
+                    for unit in grid.common_units(&roof_ids) {
+                        // Type 3: the extras form a naked subset with other cells of the unit.
+                        let others: Vec<usize> = unit
+                            .iter()
+                            .copied()
+                            .filter(|id| {
+                                !roof_ids.contains(id) && !grid.cells[id].candidates.is_empty()
+                            })
+                            .collect();
+                        for subset in subsets(&others, 3) {
+                            let mut digits = extras.clone();
+                            for id in &subset {
+                                digits.extend(grid.cells[id].candidates.iter().copied());
+                            }
+                            if digits.len() != subset.len() + 1 {
+                                continue;
+                            }
+                            let mut deduction =
+                                Deduction::new("Unique Rectangle Type 3", cells.clone());
+                            for &id in others.iter().filter(|id| !subset.contains(id)) {
+                                for &value in &digits {
+                                    if grid.has(id, value) {
+                                        deduction.eliminate(id, value);
+                                    }
+                                }
+                            }
+                            if !deduction.is_empty() {
+                                deductions.push(deduction);
+                            }
+                        }
+
+                        // Type 4: one of the pair is locked to the roofs in their unit.
+                        for (locked, other) in [(a, b), (b, a)] {
+                            if grid.positions_in(unit, locked).len() == 2 {
+                                let mut deduction =
+                                    Deduction::new("Unique Rectangle Type 4", cells.clone());
+                                for &id in &roof_ids {
+                                    deduction.eliminate(id, other);
+                                }
+                                deductions.push(deduction);
+                            }
+                        }
+                    }
+                } else if opposite(roofs[0]) == roofs[1] {
+                    // Type 6: diagonal roofs, `x` locked to the rectangle in both rows or columns.
+                    let rows = [[corners[0], corners[1]], [corners[2], corners[3]]];
+                    let columns = [[corners[0], corners[2]], [corners[1], corners[3]]];
+                    let locked_in = |lines: &[[usize; 2]; 2], value: u8| {
+                        lines.iter().all(|pair| {
+                            grid.common_units(pair)
+                                .iter()
+                                .any(|unit| grid.positions_in(unit, value).len() == 2)
+                        })
+                    };
+                    for x in [a, b] {
+                        let locked = locked_in(&rows, x) || locked_in(&columns, x);
+                        if locked {
+                            let mut deduction =
+                                Deduction::new("Unique Rectangle Type 6", cells.clone());
+                            for &id in &roof_ids {
+                                deduction.eliminate(id, x);
+                            }
+                            deductions.push(deduction);
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn requires_unique_solution(&self) -> bool {
+        true
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &[
+            "Unique Rectangle Type 1",
+            "Unique Rectangle Type 2",
+            "Unique Rectangle Type 3",
+            "Unique Rectangle Type 4",
+            "Unique Rectangle Type 5",
+            "Unique Rectangle Type 6",
+        ]
+    }
+}
+
+/// Bivalue Universal Grave + 1: every unsolved cell but one is bivalue, so the
+/// digit appearing three times in the units of that cell must be placed there.
+#[derive(Debug)]
+pub struct BugPlusOneStrategy;
+
+impl Display for BugPlusOneStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BugPlusOneStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for BugPlusOneStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let grid = Grid::new(sudoku);
+        let unsolved: Vec<(&usize, &GridCell)> = grid
+            .cells
+            .iter()
+            .filter(|(_, cell)| cell.value.is_none())
+            .collect();
+        if unsolved
+            .iter()
+            .any(|(_, cell)| !(2..=3).contains(&cell.candidates.len()))
+        {
+            return Vec::new();
+        }
+        let triple: Vec<usize> = unsolved
+            .iter()
+            .filter(|(_, cell)| cell.candidates.len() == 3)
+            .map(|(id, _)| **id)
+            .collect();
+        if triple.len() != 1 {
+            return Vec::new();
+        }
+        let id = triple[0];
+
+        // Without the extra digit every candidate would appear exactly twice per unit.
+        for &value in &grid.cells[&id].candidates {
+            let is_bug = grid.units.iter().all(|unit| {
+                (1..=S::LENGTH as u8).all(|digit| {
+                    let count = grid.positions_in(unit, digit).len();
+                    let extra = usize::from(digit == value && unit.contains(&id));
+                    count == 0 || count == 2 + extra
+                })
+            });
+            if is_bug {
+                let mut deduction = Deduction::new("BUG+1", vec![id]);
+                deduction.place(id, value);
+                return vec![deduction];
+            }
+        }
+        Vec::new()
+    }
+
+    fn requires_unique_solution(&self) -> bool {
+        true
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["BUG+1"]
+    }
+}
+
+/// Avoidable Rectangles: a rectangle of cells solved while solving (not givens)
+/// must not be completable into a swappable `{a, b}` pattern.
+#[derive(Debug)]
+pub struct AvoidableRectangleStrategy;
+
+impl Display for AvoidableRectangleStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AvoidableRectangleStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for AvoidableRectangleStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let grid = Grid::new(sudoku);
+        let mut deductions = Vec::new();
+        let found_value = |id: usize| {
+            let cell = &grid.cells[&id];
+            if cell.given { None } else { cell.value }
+        };
+
+        for corners in grid.rectangles() {
+            if corners.iter().any(|id| grid.cells[id].given) {
+                continue;
+            }
+            let cells = corners.to_vec();
+            let unsolved: Vec<usize> = (0..4)
+                .filter(|&idx| grid.cells[&corners[idx]].value.is_none())
+                .collect();
+
+            match unsolved.len() {
+                1 => {
+                    // Type 1: the open corner can't repeat its diagonal opposite.
+                    let open = unsolved[0];
+                    let diagonal = found_value(corners[opposite(open)]);
+                    let adjacent: Vec<Option<u8>> = (0..4)
+                        .filter(|&idx| idx != open && idx != opposite(open))
+                        .map(|idx| found_value(corners[idx]))
+                        .collect();
+                    if let (Some(a), Some(b)) = (diagonal, adjacent[0])
+                        && adjacent[1] == Some(b)
+                        && a != b
+                        && grid.has(corners[open], a)
+                    {
+                        let mut deduction = Deduction::new("Avoidable Rectangle Type 1", cells);
+                        deduction.eliminate(corners[open], a);
+                        deductions.push(deduction);
+                    }
+                }
+                2 if opposite(unsolved[0]) != unsolved[1] => {
+                    // Type 2: solved `a`, `b` side, open cells `{b, c}` and `{a, c}`.
+                    let solved: Vec<usize> = (0..4).filter(|idx| !unsolved.contains(idx)).collect();
+                    let (Some(a), Some(b)) = (
+                        found_value(corners[solved[0]]),
+                        found_value(corners[solved[1]]),
+                    ) else {
+                        continue;
+                    };
+                    // The open corner across from a solved one is the one not diagonal to it.
+                    let across = |idx: usize| {
+                        unsolved
+                            .iter()
+                            .copied()
+                            .find(|&open| open != opposite(idx))
+                            .unwrap()
+                    };
+                    let (under_a, under_b) =
+                        (corners[across(solved[0])], corners[across(solved[1])]);
+                    let (cands_a, cands_b) = (
+                        &grid.cells[&under_a].candidates,
+                        &grid.cells[&under_b].candidates,
+                    );
+                    if cands_a.len() != 2 || cands_b.len() != 2 {
+                        continue;
+                    }
+                    if !cands_a.contains(&b) || !cands_b.contains(&a) {
+                        continue;
+                    }
+                    let extra_a = cands_a.iter().copied().find(|&value| value != b);
+                    let extra_b = cands_b.iter().copied().find(|&value| value != a);
+                    if let (Some(extra), Some(other)) = (extra_a, extra_b)
+                        && extra == other
+                    {
+                        let targets = grid.seen_by_all(&[under_a, under_b], extra);
+                        if !targets.is_empty() {
+                            let mut deduction = Deduction::new("Avoidable Rectangle Type 2", cells);
+                            for target in targets {
+                                deduction.eliminate(target, extra);
+                            }
+                            deductions.push(deduction);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        deductions
+    }
+
+    fn requires_unique_solution(&self) -> bool {
+        true
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["Avoidable Rectangle Type 1", "Avoidable Rectangle Type 2"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::{WITHOUT_ONE, with_candidates};
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+    use crate::strategies::ConstraintPropagationStrategy;
+    use crate::traits::HasSlots;
+
+    fn find<'a>(deductions: &'a [Deduction], technique: &str) -> &'a Deduction {
+        deductions
+            .iter()
+            .find(|d| d.technique() == technique)
+            .unwrap_or_else(|| panic!("{technique} is not found."))
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_1() {
+        let sudoku =
+            with_candidates(&[(1, &[1, 2]), (2, &[1, 2]), (28, &[1, 2]), (29, &[1, 2, 3])]);
+        let deductions = UniqueRectangleStrategy.deductions(&sudoku);
+        let found = find(&deductions, "Unique Rectangle Type 1");
+        assert_eq!(found.cells(), &[1, 2, 28, 29]);
+        assert_eq!(found.eliminations(), &[(29, 1), (29, 2)]);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_2() {
+        // Roofs r4c1 and r4c2 share the extra 3, which is removed from row 4 and area 4.
+        let sudoku = with_candidates(&[
+            (1, &[1, 2]),
+            (2, &[1, 2]),
+            (28, &[1, 2, 3]),
+            (29, &[1, 2, 3]),
+        ]);
+        let deductions = UniqueRectangleStrategy.deductions(&sudoku);
+        let found = find(&deductions, "Unique Rectangle Type 2");
+        assert_eq!(found.cells(), &[1, 2, 28, 29]);
+        let targets = [30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 46, 47, 48];
+        assert_eq!(found.eliminations(), targets.map(|id| (id, 3)));
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_3() {
+        // Extras 3 and 4 of the roofs form a naked pair with r4c5 {34} in row 4.
+        let sudoku = with_candidates(&[
+            (1, &[1, 2]),
+            (2, &[1, 2]),
+            (28, &[1, 2, 3]),
+            (29, &[1, 2, 4]),
+            (32, &[3, 4]),
+        ]);
+        let deductions = UniqueRectangleStrategy.deductions(&sudoku);
+        let found = find(&deductions, "Unique Rectangle Type 3");
+        let expected: Vec<(usize, u8)> = [30, 31, 33, 34, 35, 36]
+            .into_iter()
+            .flat_map(|id| [(id, 3), (id, 4)])
+            .collect();
+        assert_eq!(found.eliminations(), expected);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_4() {
+        let mut cells: Vec<(usize, &[u8])> = vec![
+            (1, &[1, 2]),
+            (2, &[1, 2]),
+            (28, &[1, 2, 3]),
+            (29, &[1, 2, 4]),
+        ];
+        cells.extend((30..=36).map(|id| (id, WITHOUT_ONE)));
+        let sudoku = with_candidates(&cells);
+        let deductions = UniqueRectangleStrategy.deductions(&sudoku);
+        let found = find(&deductions, "Unique Rectangle Type 4");
+        assert_eq!(found.eliminations(), &[(28, 2), (29, 2)]);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_5() {
+        // Diagonal roofs r1c4 and r2c1 share the extra 3, which is removed from the
+        // cells seeing both.
+        let sudoku = with_candidates(&[
+            (1, &[1, 2]),
+            (4, &[1, 2, 3]),
+            (10, &[1, 2, 3]),
+            (13, &[1, 2]),
+        ]);
+        let deductions = UniqueRectangleStrategy.deductions(&sudoku);
+        let found = find(&deductions, "Unique Rectangle Type 5");
+        assert_eq!(found.cells(), &[1, 4, 10, 13]);
+        assert_eq!(found.eliminations(), &[(2, 3), (3, 3), (14, 3), (15, 3)]);
+    }
+
+    #[test]
+    fn test_unique_rectangle_type_6() {
+        // Diagonal roofs r1c2 and r4c1, and 1 is locked to the rectangle in rows 1 and 4.
+        let mut cells: Vec<(usize, &[u8])> = vec![
+            (1, &[1, 2]),
+            (2, &[1, 2, 3]),
+            (28, &[1, 2, 3]),
+            (29, &[1, 2]),
+        ];
+        cells.extend((3..=9).chain(30..=36).map(|id| (id, WITHOUT_ONE)));
+        let sudoku = with_candidates(&cells);
+        let deductions = UniqueRectangleStrategy.deductions(&sudoku);
+        let found = find(&deductions, "Unique Rectangle Type 6");
+        assert_eq!(found.eliminations(), &[(2, 1), (28, 1)]);
+    }
+
+    #[test]
+    fn test_bug_plus_one() {
+        // Every open cell is bivalue but r7c4 {678}, where 8 appears three times.
+        let mut grid = load_csv("fixtures/hard_2_solved.csv");
+        for (row, column) in [
+            (0, 1),
+            (0, 8),
+            (1, 1),
+            (1, 8),
+            (2, 3),
+            (2, 4),
+            (6, 3),
+            (6, 4),
+            (6, 8),
+            (7, 3),
+            (7, 8),
+        ] {
+            grid[row][column] = None;
+        }
+        let mut sudoku = Sudoku9x9::new(grid);
+        ConstraintPropagationStrategy.run(&mut sudoku);
+        let deductions = BugPlusOneStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique(), "BUG+1");
+        assert_eq!(deductions[0].placements(), &[(58, 8)]);
+    }
+
+    #[test]
+    fn test_avoidable_rectangle_type_1() {
+        let sudoku = with_candidates(&[]);
+        for (id, value) in [(1, 1), (2, 2), (28, 2)] {
+            sudoku.slots()[id - 1].borrow_mut().set_value(Some(value));
+        }
+        let deductions = AvoidableRectangleStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].eliminations(), &[(29, 1)]);
+    }
+
+    #[test]
+    fn test_avoidable_rectangle_ignores_givens() {
+        let mut data = vec![vec![None; 9]; 9];
+        data[0][0] = Some(1);
+        data[0][1] = Some(2);
+        data[3][0] = Some(2);
+        let sudoku = Sudoku9x9::new(data);
+        assert!(AvoidableRectangleStrategy.deductions(&sudoku).is_empty());
+    }
+
+    #[test]
+    fn test_avoidable_rectangle_type_2() {
+        // r1c1 = 1 and r1c2 = 2 were found, r4c1 {23} and r4c2 {13} share the extra 3.
+        let sudoku = with_candidates(&[(28, &[2, 3]), (29, &[1, 3])]);
+        for (id, value) in [(1, 1), (2, 2)] {
+            sudoku.slots()[id - 1].borrow_mut().set_value(Some(value));
+        }
+        let deductions = AvoidableRectangleStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].technique(), "Avoidable Rectangle Type 2");
+        let targets = [30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 46, 47, 48];
+        assert_eq!(deductions[0].eliminations(), targets.map(|id| (id, 3)));
+    }
+
+    #[test]
+    fn test_uniqueness_requires_unique_solution() {
+        let strategies: Vec<Box<dyn Strategy<Sudoku9x9>>> = vec![
+            Box::new(UniqueRectangleStrategy),
+            Box::new(BugPlusOneStrategy),
+            Box::new(AvoidableRectangleStrategy),
+        ];
+        assert!(strategies.iter().all(|s| s.requires_unique_solution()));
+    }
+}
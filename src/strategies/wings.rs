@@ -0,0 +1,324 @@
+use crate::helpers::{peer_ids, positions_of, run_deductions};
+use crate::objects::deduction::Deduction;
+use crate::traits::{Identifiable, SimpleSudoku, Strategy};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// Unsolved slot with its (sorted) candidates and the ids of its peers.
+struct WingCell {
+    id: usize,
+    candidates: Vec<u8>,
+    peers: HashSet<usize>,
+}
+
+fn collect_cells<S: SimpleSudoku>(sudoku: &S) -> HashMap<usize, WingCell> {
+    sudoku
+        .slots()
+        .iter()
+        .filter(|slot| !slot.borrow().is_solved())
+        .map(|slot| {
+            let slot = slot.borrow();
+            let mut candidates: Vec<u8> = slot.candidates().into_iter().collect();
+            candidates.sort_unstable();
+            let cell = WingCell {
+                id: slot.id(),
+                candidates,
+                peers: peer_ids(&slot),
+            };
+            (cell.id, cell)
+        })
+        .collect()
+}
+
+/// Sorted ids of unsolved cells, to keep the search order stable.
+fn sorted_ids(cells: &HashMap<usize, WingCell>) -> Vec<usize> {
+    let mut ids: Vec<usize> = cells.keys().copied().collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Eliminate `value` from every cell seeing all of `seen_by`, except the pattern cells.
+fn eliminate_from_common_peers(
+    cells: &HashMap<usize, WingCell>,
+    deduction: &mut Deduction,
+    seen_by: &[usize],
+    value: u8,
+) {
+    let mut targets: Vec<usize> = cells[&seen_by[0]].peers.iter().copied().collect();
+    targets.sort_unstable();
+    for target in targets {
+        if deduction.cells().contains(&target) {
+            continue;
+        }
+        let Some(cell) = cells.get(&target) else {
+            continue;
+        };
+        if cell.candidates.contains(&value)
+            && seen_by[1..]
+                .iter()
+                .all(|id| cells[id].peers.contains(&target))
+        {
+            deduction.eliminate(target, value);
+        }
+    }
+}
+
+/// Pivot `{x, y}` seeing pincers `{x, z}` and `{y, z}`: `z` can be removed from
+/// every cell seeing both pincers.
+#[derive(Debug)]
+pub struct XYWingStrategy;
+
+impl Display for XYWingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XYWingStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for XYWingStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let cells = collect_cells(sudoku);
+        let mut deductions = Vec::new();
+
+        for pivot_id in sorted_ids(&cells) {
+            let pivot = &cells[&pivot_id];
+            if pivot.candidates.len() != 2 {
+                continue;
+            }
+            let (x, y) = (pivot.candidates[0], pivot.candidates[1]);
+            let mut bivalue_peers: Vec<&WingCell> = pivot
+                .peers
+                .iter()
+                .filter_map(|id| cells.get(id))
+                .filter(|cell| cell.candidates.len() == 2)
+                .collect();
+            bivalue_peers.sort_unstable_by_key(|cell| cell.id);
+
+            for first in &bivalue_peers {
+                // The first pincer holds `x` and some `z`.
+                if !first.candidates.contains(&x) || first.candidates.contains(&y) {
+                    continue;
+                }
+                let z = if first.candidates[0] == x {
+                    first.candidates[1]
+                } else {
+                    first.candidates[0]
+                };
+                for second in &bivalue_peers {
+                    if second.candidates != sorted_pair(y, z) {
+                        continue;
+                    }
+                    let mut deduction =
+                        Deduction::new("XY-Wing", vec![pivot.id, first.id, second.id]);
+                    eliminate_from_common_peers(&cells, &mut deduction, &[first.id, second.id], z);
+                    if !deduction.is_empty() {
+                        deductions.push(deduction);
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["XY-Wing"]
+    }
+}
+
+fn sorted_pair(a: u8, b: u8) -> Vec<u8> {
+    if a < b { vec![a, b] } else { vec![b, a] }
+}
+
+/// Pivot `{x, y, z}` seeing pincers `{x, z}` and `{y, z}`: `z` can be removed from
+/// every cell seeing the pivot and both pincers.
+#[derive(Debug)]
+pub struct XYZWingStrategy;
+
+impl Display for XYZWingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XYZWingStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for XYZWingStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let cells = collect_cells(sudoku);
+        let mut deductions = Vec::new();
+
+        for pivot_id in sorted_ids(&cells) {
+            let pivot = &cells[&pivot_id];
+            if pivot.candidates.len() != 3 {
+                continue;
+            }
+            let mut bivalue_peers: Vec<&WingCell> = pivot
+                .peers
+                .iter()
+                .filter_map(|id| cells.get(id))
+                .filter(|cell| {
+                    cell.candidates.len() == 2
+                        && cell.candidates.iter().all(|c| pivot.candidates.contains(c))
+                })
+                .collect();
+            bivalue_peers.sort_unstable_by_key(|cell| cell.id);
+
+            for &z in &pivot.candidates {
+                let others: Vec<u8> = pivot
+                    .candidates
+                    .iter()
+                    .copied()
+                    .filter(|&c| c != z)
+                    .collect();
+                let (x, y) = (others[0], others[1]);
+                for first in bivalue_peers
+                    .iter()
+                    .filter(|c| c.candidates == sorted_pair(x, z))
+                {
+                    for second in bivalue_peers
+                        .iter()
+                        .filter(|c| c.candidates == sorted_pair(y, z))
+                    {
+                        let mut deduction =
+                            Deduction::new("XYZ-Wing", vec![pivot.id, first.id, second.id]);
+                        eliminate_from_common_peers(
+                            &cells,
+                            &mut deduction,
+                            &[pivot.id, first.id, second.id],
+                            z,
+                        );
+                        if !deduction.is_empty() {
+                            deductions.push(deduction);
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["XYZ-Wing"]
+    }
+}
+
+/// Two non-seeing cells `{x, y}` connected by a strong link on `x` (the only two
+/// places for `x` in some unit, each seeing one of the cells): `y` can be removed
+/// from every cell seeing both of them.
+#[derive(Debug)]
+pub struct WWingStrategy;
+
+impl Display for WWingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WWingStrategy")
+    }
+}
+
+impl<S: SimpleSudoku> Strategy<S> for WWingStrategy {
+    fn run(&self, sudoku: &mut S) {
+        run_deductions(self, sudoku);
+    }
+
+    fn deductions(&self, sudoku: &S) -> Vec<Deduction> {
+        let cells = collect_cells(sudoku);
+        let units = sudoku.units();
+        let mut deductions = Vec::new();
+
+        let bivalue: Vec<&WingCell> = sorted_ids(&cells)
+            .into_iter()
+            .map(|id| &cells[&id])
+            .filter(|cell| cell.candidates.len() == 2)
+            .collect();
+
+        for (idx, first) in bivalue.iter().enumerate() {
+            for second in &bivalue[idx + 1..] {
+                if first.candidates != second.candidates || first.peers.contains(&second.id) {
+                    continue;
+                }
+                for (link, other) in [
+                    (first.candidates[0], first.candidates[1]),
+                    (first.candidates[1], first.candidates[0]),
+                ] {
+                    for unit in &units {
+                        let positions = positions_of(unit, link);
+                        if positions.len() != 2
+                            || positions.contains(&first.id)
+                            || positions.contains(&second.id)
+                        {
+                            continue;
+                        }
+                        let (p, q) = (positions[0], positions[1]);
+                        let linked = (first.peers.contains(&p) && second.peers.contains(&q))
+                            || (first.peers.contains(&q) && second.peers.contains(&p));
+                        if !linked {
+                            continue;
+                        }
+                        let mut deduction =
+                            Deduction::new("W-Wing", vec![first.id, second.id, p, q]);
+                        eliminate_from_common_peers(
+                            &cells,
+                            &mut deduction,
+                            &[first.id, second.id],
+                            other,
+                        );
+                        if !deduction.is_empty() {
+                            deductions.push(deduction);
+                        }
+                    }
+                }
+            }
+        }
+        deductions
+    }
+
+    fn techniques(&self) -> &'static [&'static str] {
+        &["W-Wing"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::test_utils::with_candidates;
+
+    #[test]
+    fn test_xy_wing() {
+        // Pivot r1c1 {1,2}, pincers r1c5 {1,3} and r5c1 {2,3}.
+        let sudoku = with_candidates(&[(1, &[1, 2]), (5, &[1, 3]), (37, &[2, 3])]);
+        let deductions = XYWingStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].cells(), &[1, 5, 37]);
+        assert_eq!(deductions[0].eliminations(), &[(41, 3)]);
+    }
+
+    #[test]
+    fn test_xyz_wing() {
+        // Pivot r1c1 {1,2,3}, pincers r1c2 {1,3} and r2c1 {2,3}.
+        let sudoku = with_candidates(&[(1, &[1, 2, 3]), (2, &[1, 3]), (10, &[2, 3])]);
+        let deductions = XYZWingStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(
+            deductions[0].eliminations(),
+            &[(3, 3), (11, 3), (12, 3), (19, 3), (20, 3), (21, 3)]
+        );
+    }
+
+    #[test]
+    fn test_w_wing() {
+        // Cells r1c1 and r5c5 {1,2}, linked by the only 1s of row 9 in c1 and c5.
+        let mut cells: Vec<(usize, &[u8])> = vec![(1, &[1, 2]), (41, &[1, 2])];
+        for slot_id in [74, 75, 76, 78, 79, 80, 81] {
+            cells.push((slot_id, &[2, 3, 4, 5, 6, 7, 8, 9]));
+        }
+        let sudoku = with_candidates(&cells);
+        let deductions = WWingStrategy.deductions(&sudoku);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].cells(), &[1, 41, 73, 77]);
+        assert_eq!(deductions[0].eliminations(), &[(5, 2), (37, 2)]);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::objects::area::Area;
+use crate::objects::budget::Budget;
+use crate::objects::deduction::Deduction;
 use crate::objects::line::{Alignment, Line};
 use crate::objects::slot::Slot;
+use crate::observer::SolverObserver;
 use std::cell::RefCell;
 use std::rc::Rc;
 use tracing::trace;
@@ -55,6 +58,15 @@ pub trait SolveMetrics: Solvable {
             .count()
     }
 
+    /// Total number of candidates left in unsolved slots.
+    fn count_candidates(&self) -> usize {
+        self.slots()
+            .iter()
+            .filter(|slot| !slot.borrow().is_solved())
+            .map(|slot| slot.borrow().candidate_count())
+            .sum()
+    }
+
     fn count_solved_percentage(&self) -> f64 {
         self.count_solved_slots() as f64 * 100.0 / self.slots().len() as f64
     }
@@ -189,6 +201,20 @@ pub trait SimpleSudoku:
         areas: Vec<Rc<RefCell<Area>>>,
     ) -> Self;
 
+    /// All units (rows, columns and areas) as lists of their slots.
+    fn units(&self) -> Vec<Vec<Rc<RefCell<Slot>>>> {
+        let rows = self.rows().iter().map(|row| row.borrow().slots().clone());
+        let columns = self
+            .columns()
+            .iter()
+            .map(|column| column.borrow().slots().clone());
+        let areas = self
+            .areas()
+            .iter()
+            .map(|area| area.borrow().slots().clone());
+        rows.chain(columns).chain(areas).collect()
+    }
+
     fn display_slots_ids(&self) {
         let slots_ids: Vec<String> = self
             .slots()
@@ -242,6 +268,40 @@ pub trait SimpleSudoku:
 
 pub trait Strategy<S: SimpleSudoku> {
     fn run(&self, sudoku: &mut S);
+
+    /// Deductions the strategy can make on the current grid, without applying them.
+    fn deductions(&self, _sudoku: &S) -> Vec<Deduction> {
+        Vec::new()
+    }
+
+    /// `run` honouring a budget. The solver checks the budget between strategies.
+    /// Searches, chains, forcing and template strategies also check it while working,
+    /// and apply only what they found before it ran out.
+    fn run_within(&self, sudoku: &mut S, _budget: &Budget) {
+        self.run(sudoku);
+    }
+
+    /// `run_within` reporting search guesses and undos to the observer. Only search
+    /// strategies guess, the others just run.
+    fn run_observed(&self, sudoku: &mut S, budget: &Budget, _observer: &mut dyn SolverObserver) {
+        self.run_within(sudoku, budget);
+    }
+
+    /// Name for logs and observers, the type name by default.
+    fn name(&self) -> &'static str {
+        let path = std::any::type_name::<Self>();
+        path.rsplit("::").next().unwrap_or(path)
+    }
+
+    /// Technique labels of the deductions the strategy makes, none by default.
+    fn techniques(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether the strategy is only sound for puzzles with a unique solution.
+    fn requires_unique_solution(&self) -> bool {
+        false
+    }
 }
 
 pub trait Solver<S: SimpleSudoku> {
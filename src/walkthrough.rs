@@ -0,0 +1,263 @@
+use crate::helpers::cell_name;
+use crate::objects::deduction::Deduction;
+use crate::solver::{GridSnapshot, Solver};
+use crate::traits::SimpleSudoku;
+use std::fmt::{Display, Write};
+
+/// One deduction of a walkthrough, with slots named in r1c1 notation.
+#[derive(Debug, Clone)]
+pub struct WalkthroughStep {
+    pub technique: &'static str,
+    pub cells: Vec<String>,
+    /// e.g. "r1c1=5".
+    pub placements: Vec<String>,
+    /// e.g. "r1c1<>5".
+    pub eliminations: Vec<String>,
+    pub explanation: Option<String>,
+    /// Grid after the step.
+    pub grid: GridSnapshot,
+}
+
+impl WalkthroughStep {
+    fn new<S: SimpleSudoku>(deduction: &Deduction, sudoku: &S) -> Self {
+        let name = |&(slot_id, value): &(usize, u8), operator: &str| {
+            format!("{}{operator}{value}", cell_name(sudoku, slot_id))
+        };
+        Self {
+            technique: deduction.technique(),
+            cells: deduction
+                .cells()
+                .iter()
+                .map(|&slot_id| cell_name(sudoku, slot_id))
+                .collect(),
+            placements: deduction
+                .placements()
+                .iter()
+                .map(|p| name(p, "="))
+                .collect(),
+            eliminations: deduction
+                .eliminations()
+                .iter()
+                .map(|e| name(e, "<>"))
+                .collect(),
+            explanation: deduction.explanation().map(str::to_string),
+            grid: GridSnapshot::of(sudoku),
+        }
+    }
+}
+
+/// Every step of a logical solve in order, exportable as Markdown or HTML.
+#[derive(Debug, Clone)]
+pub struct Walkthrough {
+    pub initial: GridSnapshot,
+    pub steps: Vec<WalkthroughStep>,
+    pub solved: bool,
+    /// Side of the grid and size of an area as (rows, columns), for drawing.
+    length: usize,
+    area: (usize, usize),
+}
+
+impl Walkthrough {
+    /// Solve step by step with the solver's strategies, recording every deduction.
+    /// Backtracking is never used, so the walkthrough may end on an unsolved grid.
+    pub fn record<S: SimpleSudoku + Display>(solver: &mut Solver<'_, S>) -> Self {
+        let initial = GridSnapshot::of(solver.sudoku());
+        let mut steps = Vec::new();
+        while let Some(deduction) = solver.step() {
+            steps.push(WalkthroughStep::new(&deduction, solver.sudoku()));
+        }
+        Self {
+            initial,
+            steps,
+            solved: solver.sudoku().is_solved(),
+            length: S::LENGTH,
+            area: (S::NUMBER_OF_ROWS_IN_AREA, S::NUMBER_OF_COLS_IN_AREA),
+        }
+    }
+
+    fn summary(&self) -> String {
+        let result = if self.solved { "solved" } else { "stuck" };
+        format!("{} steps, {result}.", self.steps.len())
+    }
+
+    /// Grid as text, `.` for open slots and lines between areas.
+    fn text_grid(&self, grid: &GridSnapshot) -> String {
+        let mut text = String::new();
+        for (row, values) in grid.values.chunks(self.length).enumerate() {
+            if row > 0 && row % self.area.0 == 0 {
+                let segment = "-".repeat(self.area.1 * 2 - 1);
+                let segments = vec![segment; self.length / self.area.1];
+                writeln!(text, "{}", segments.join("-+-")).expect("Writing to a String.");
+            }
+            let cells: Vec<String> = values
+                .iter()
+                .enumerate()
+                .map(|(column, value)| {
+                    let separator = if column > 0 && column % self.area.1 == 0 {
+                        "| "
+                    } else {
+                        ""
+                    };
+                    let value = value.map_or(".".to_string(), |value| value.to_string());
+                    format!("{separator}{value}")
+                })
+                .collect();
+            writeln!(text, "{}", cells.join(" ")).expect("Writing to a String.");
+        }
+        text
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Walkthrough\n\n");
+        markdown.push_str(&format!("{}\n\n", self.summary()));
+        markdown.push_str("## Initial grid\n\n");
+        markdown.push_str(&format!("```text\n{}```\n", self.text_grid(&self.initial)));
+        for (number, step) in self.steps.iter().enumerate() {
+            markdown.push_str(&format!("\n## Step {}: {}\n\n", number + 1, step.technique));
+            markdown.push_str(&format!("- Cells: {}\n", step.cells.join(" ")));
+            if let Some(explanation) = &step.explanation {
+                markdown.push_str(&format!("- Pattern: `{explanation}`\n"));
+            }
+            if !step.placements.is_empty() {
+                markdown.push_str(&format!("- Placements: {}\n", step.placements.join(", ")));
+            }
+            if !step.eliminations.is_empty() {
+                let eliminations = step.eliminations.join(", ");
+                markdown.push_str(&format!("- Eliminations: {eliminations}\n"));
+            }
+            markdown.push_str(&format!("\n```text\n{}```\n", self.text_grid(&step.grid)));
+        }
+        markdown
+    }
+
+    /// Grid as an HTML table, slots placed by the step are highlighted.
+    fn html_grid(&self, grid: &GridSnapshot, placed: &[usize]) -> String {
+        let mut html = String::from("<table class=\"grid\">\n");
+        for (row, values) in grid.values.chunks(self.length).enumerate() {
+            html.push_str("<tr>");
+            for (column, value) in values.iter().enumerate() {
+                let mut classes = Vec::new();
+                if placed.contains(&(row * self.length + column)) {
+                    classes.push("placed");
+                }
+                if (column + 1) % self.area.1 == 0 && column + 1 < self.length {
+                    classes.push("right");
+                }
+                if (row + 1) % self.area.0 == 0 && row + 1 < self.length {
+                    classes.push("bottom");
+                }
+                let value = value.map_or(String::new(), |value| value.to_string());
+                html.push_str(&format!("<td class=\"{}\">{value}</td>", classes.join(" ")));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+        html
+    }
+
+    /// Self-contained HTML page, styles included.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Walkthrough</title>\n<style>\n\
+             body { font-family: sans-serif; max-width: 48em; margin: auto; }\n\
+             table.grid { border-collapse: collapse; border: 2px solid #000; }\n\
+             table.grid td { width: 1.6em; height: 1.6em; text-align: center; \
+             border: 1px solid #999; }\n\
+             table.grid td.right { border-right: 2px solid #000; }\n\
+             table.grid td.bottom { border-bottom: 2px solid #000; }\n\
+             table.grid td.placed { background: #cde8ff; font-weight: bold; }\n\
+             </style>\n</head>\n<body>\n<h1>Walkthrough</h1>\n",
+        );
+        html.push_str(&format!("<p>{}</p>\n", self.summary()));
+        html.push_str("<h2>Initial grid</h2>\n");
+        html.push_str(&self.html_grid(&self.initial, &[]));
+        let mut previous = &self.initial;
+        for (number, step) in self.steps.iter().enumerate() {
+            html.push_str(&format!(
+                "<section>\n<h2>Step {}: {}</h2>\n<ul>\n",
+                number + 1,
+                escape(step.technique)
+            ));
+            html.push_str(&format!(
+                "<li>Cells: {}</li>\n",
+                escape(&step.cells.join(" "))
+            ));
+            if let Some(explanation) = &step.explanation {
+                html.push_str(&format!(
+                    "<li>Pattern: <code>{}</code></li>\n",
+                    escape(explanation)
+                ));
+            }
+            if !step.placements.is_empty() {
+                let placements = escape(&step.placements.join(", "));
+                html.push_str(&format!("<li>Placements: {placements}</li>\n"));
+            }
+            if !step.eliminations.is_empty() {
+                let eliminations = escape(&step.eliminations.join(", "));
+                html.push_str(&format!("<li>Eliminations: {eliminations}</li>\n"));
+            }
+            html.push_str("</ul>\n");
+            let placed: Vec<usize> = (0..step.grid.values.len())
+                .filter(|&idx| step.grid.values[idx] != previous.values[idx])
+                .collect();
+            html.push_str(&self.html_grid(&step.grid, &placed));
+            previous = &step.grid;
+            html.push_str("</section>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objects::sudoku::Sudoku9x9;
+    use crate::parsers::load_csv;
+
+    fn walkthrough(filename: &str) -> Walkthrough {
+        let mut sudoku = Sudoku9x9::new(load_csv(filename));
+        let mut solver = Solver::with_defaults(&mut sudoku);
+        solver.set_assume_unique(true);
+        Walkthrough::record(&mut solver)
+    }
+
+    #[test]
+    fn test_markdown() {
+        let walkthrough = walkthrough("fixtures/easy.csv");
+        assert!(walkthrough.solved);
+        let markdown = walkthrough.to_markdown();
+        assert!(markdown.starts_with("# Walkthrough\n"));
+        assert!(markdown.contains(&format!("{} steps, solved.", walkthrough.steps.len())));
+        assert!(markdown.contains("## Step 1: "));
+        assert!(markdown.contains(&walkthrough.steps[0].placements[0]));
+        // The last grid is complete.
+        let last = walkthrough.text_grid(&walkthrough.steps.last().unwrap().grid);
+        assert!(!last.contains('.'));
+        assert_eq!(last.lines().count(), 11);
+        assert_eq!(last.lines().nth(3), Some("------+-------+------"));
+    }
+
+    #[test]
+    fn test_html() {
+        let walkthrough = walkthrough("fixtures/hard_1.csv");
+        let html = walkthrough.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>\n"));
+        assert_eq!(html.matches("<section>").count(), walkthrough.steps.len());
+        assert!(html.contains("class=\"placed"));
+        assert!(!html.contains("<>"));
+        assert_eq!(
+            escape("r1c1<>5 & \"x\""),
+            "r1c1&lt;&gt;5 &amp; &quot;x&quot;"
+        );
+    }
+}